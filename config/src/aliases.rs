@@ -0,0 +1,85 @@
+use crate::ConfigError;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::sync::{Arc, OnceLock, RwLock};
+
+static ALIASES: OnceLock<RwLock<Arc<Aliases>>> = OnceLock::new();
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Aliases {
+  #[serde(default)]
+  assets: HashMap<String, HashMap<String, String>>,
+  #[serde(default)]
+  inbound: HashMap<String, HashMap<String, String>>,
+  #[serde(default)]
+  networks: HashMap<String, HashMap<String, String>>,
+}
+
+impl Aliases {
+  pub fn get() -> Arc<Self> {
+    Self::get_registry().read().unwrap().clone()
+  }
+
+  // Replaces the installed aliases, so a reload applies to later lookups.
+  pub fn set(aliases: Self) {
+    *Self::get_registry().write().unwrap() = Arc::new(aliases);
+  }
+
+  fn get_registry() -> &'static RwLock<Arc<Self>> {
+    ALIASES.get_or_init(|| RwLock::new(Arc::new(Self::get_builtin())))
+  }
+
+  pub fn load(path: &str) -> Result<Self, ConfigError> {
+    if path.is_empty() {
+      return Ok(Self::get_builtin());
+    }
+
+    let aliases = read_to_string(path).map_err(|err| ConfigError::Read(path.to_string(), err))?;
+
+    Self::parse(&aliases).map_err(|err| ConfigError::Aliases(path.to_string(), err))
+  }
+
+  fn get_builtin() -> Self {
+    Self::parse(include_str!("../aliases.toml"))
+      .unwrap_or_else(|err| panic!("invalid built-in aliases.toml: {err}"))
+  }
+
+  pub fn parse(aliases: &str) -> Result<Self, toml::de::Error> {
+    toml::from_str(aliases)
+  }
+
+  pub fn get_ticker(&self, venue: &str, asset: &str) -> String {
+    self
+      .assets
+      .get(asset)
+      .and_then(|venues| venues.get(venue))
+      .map_or(asset, String::as_str)
+      .to_string()
+  }
+
+  pub fn get_key(&self, venue: &str, ticker: &str) -> String {
+    let inbound = self
+      .inbound
+      .get(venue)
+      .and_then(|tickers| tickers.get(ticker));
+    let asset = self
+      .assets
+      .iter()
+      .find(|(_, venues)| venues.get(venue).map(String::as_str) == Some(ticker))
+      .map(|(asset, _)| asset);
+
+    inbound.or(asset).map_or(ticker, String::as_str).to_string()
+  }
+
+  pub fn get_network(&self, venue: &str, network: &str) -> String {
+    let network = network.to_lowercase();
+
+    self
+      .networks
+      .get(venue)
+      .and_then(|networks| networks.get(&network))
+      .cloned()
+      .unwrap_or(network)
+  }
+}
@@ -0,0 +1,468 @@
+use crate::aliases::Aliases;
+use crate::fees::Fees;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::Path;
+use toml::{Table, Value};
+
+const CONFIG_FILE: &str = "hodler.toml";
+const CONFIG_FILE_VAR: &str = "HODLER_CONFIG";
+const ENV_PREFIX: &str = "HODLER_";
+// Option fields default to None, which leaves them out of the serialized defaults.
+const OPTIONAL_FIELDS: [&str; 1] = ["reconnect_max_attempts"];
+const CREDENTIALS: [&str; 3] = ["_api_key", "_api_secret", "_api_passphrase"];
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+  pub port: u16,
+  pub logging_level: String,
+  pub signal_threshold: f32,
+  pub signal_exit_threshold: f32,
+  pub signal_persistence_ms: u64,
+  pub signal_cooldown_ms: u64,
+  #[serde(deserialize_with = "deserialize_list")]
+  pub symbols: Vec<String>,
+  pub symbol_discovery: bool,
+  pub aliases: String,
+  #[serde(deserialize_with = "deserialize_list")]
+  pub exchanges: Vec<String>,
+  #[serde(skip)]
+  pub listings: BTreeMap<String, Vec<String>>,
+  pub reconnect_initial_delay_ms: u64,
+  pub reconnect_max_delay_ms: u64,
+  pub reconnect_multiplier: f64,
+  pub reconnect_jitter: f64,
+  pub reconnect_max_attempts: Option<u32>,
+  pub feed_idle_timeout_ms: u64,
+  pub order_books: bool,
+  pub order_book_depth: usize,
+  pub trades: bool,
+  pub vwap_window_ms: i64,
+  pub binance_max_streams_per_connection: usize,
+  pub binance_subscribe_over_socket: bool,
+  pub binance_book_ticker: bool,
+  pub binance_trade_stream: String,
+  #[serde(deserialize_with = "deserialize_list")]
+  pub binance_triangle_quotes: Vec<String>,
+  pub triangle_max_quote_age_ms: i64,
+  pub bitkub_depth_poll_interval_ms: u64,
+  pub fees: BTreeMap<String, Fees>,
+  pub net_rate_notional: f32,
+  pub network_status: bool,
+  pub network_status_poll_interval_ms: u64,
+  pub binance_api_key: String,
+  pub binance_api_secret: String,
+  pub bitkub_api_key: String,
+  pub bitkub_api_secret: String,
+  pub kraken_api_key: String,
+  pub kraken_api_secret: String,
+  pub okx_api_key: String,
+  pub okx_api_secret: String,
+  pub okx_api_passphrase: String,
+  pub bybit_api_key: String,
+  pub bybit_api_secret: String,
+  pub fx: bool,
+  pub fx_url: String,
+  pub fx_poll_interval_ms: u64,
+  pub fx_max_age_ms: i64,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+  Read(String, std::io::Error),
+  Parse(toml::de::Error),
+  Aliases(String, toml::de::Error),
+  Invalid(&'static str, String),
+}
+
+impl Config {
+  pub fn load() -> Result<Self, ConfigError> {
+    let path = std::env::var(CONFIG_FILE_VAR).ok();
+    let file = match read_to_string(path.as_deref().unwrap_or(CONFIG_FILE)) {
+      Ok(file) => file,
+      Err(err) if err.kind() == ErrorKind::NotFound && path.is_none() => String::new(),
+      Err(err) => return Err(ConfigError::Read(path.unwrap_or_default(), err)),
+    };
+    let mut config = Self::from_sources(&file, std::env::vars())?;
+
+    // Relative alias paths are read next to the config file, not the CWD.
+    if let Some(dir) = path.as_deref().and_then(|path| Path::new(path).parent()) {
+      if !config.aliases.is_empty() {
+        config.aliases = dir.join(&config.aliases).to_string_lossy().to_string();
+      }
+    }
+
+    Aliases::set(Aliases::load(&config.aliases)?);
+    Ok(config)
+  }
+
+  pub fn from_sources<I>(file: &str, vars: I) -> Result<Self, ConfigError>
+  where
+    I: IntoIterator<Item = (String, String)>,
+  {
+    let mut table = file.parse::<Table>().map_err(ConfigError::Parse)?;
+    let defaults = Table::try_from(Self::default()).unwrap_or_default();
+
+    vars
+      .into_iter()
+      .filter(|(name, _)| name.starts_with(ENV_PREFIX) && name != CONFIG_FILE_VAR)
+      .map(|(name, value)| (name[ENV_PREFIX.len()..].to_lowercase(), value))
+      .filter(|(key, _)| defaults.contains_key(key) || OPTIONAL_FIELDS.contains(&key.as_str()))
+      .for_each(|(key, value)| {
+        let value = match defaults.get(&key) {
+          Some(Value::String(_)) => Value::String(value),
+          _ => parse_env_value(value),
+        };
+        merge_table(&mut table, Table::from_iter([(key, value)]));
+      });
+
+    if let Some(Value::Table(fees)) = table.get("fees") {
+      let mut defaults = Table::try_from(Fees::get_defaults()).unwrap_or_default();
+      merge_table(&mut defaults, fees.clone());
+      table.insert("fees".to_string(), Value::Table(defaults));
+    }
+
+    let config = Value::Table(table)
+      .try_into::<Self>()
+      .map_err(ConfigError::Parse)?;
+    config.validate()?;
+
+    Ok(config)
+  }
+
+  pub fn get_changes(&self, config: &Config) -> Vec<String> {
+    let mut changes = Vec::new();
+    let mut old = BTreeMap::new();
+    let mut new = BTreeMap::new();
+    flatten_table("", Table::try_from(self).unwrap_or_default(), &mut old);
+    flatten_table("", Table::try_from(config).unwrap_or_default(), &mut new);
+
+    get_list_changes("symbols", &self.symbols, &config.symbols, &mut changes);
+    get_list_changes(
+      "exchanges",
+      &self.exchanges,
+      &config.exchanges,
+      &mut changes,
+    );
+
+    let mut keys = old.keys().chain(new.keys()).collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+    keys
+      .into_iter()
+      .filter(|key| *key != "symbols" && *key != "exchanges")
+      .filter(|key| old.get(*key) != new.get(*key))
+      .for_each(|key| {
+        let get = |table: &BTreeMap<String, Value>| match table.get(key) {
+          Some(_) if CREDENTIALS.iter().any(|field| key.ends_with(field)) => {
+            "<redacted>".to_string()
+          }
+          Some(Value::Float(value)) => (*value as f32).to_string(),
+          Some(value) => value.to_string(),
+          None => "unset".to_string(),
+        };
+        changes.push(format!("{key}: {} -> {}", get(&old), get(&new)));
+      });
+
+    changes
+  }
+
+  pub fn get_feed_changes(&self, config: &Config, exchange: &str) -> Vec<String> {
+    let venue = format!("{exchange}_");
+    let prefixes = ["reconnect_", "feed_", "order_book", "trades", &venue];
+
+    self
+      .get_changes(config)
+      .into_iter()
+      .filter(|change| {
+        let key = change.split(':').next().unwrap_or_default();
+        !key.contains("_api_") && prefixes.iter().any(|prefix| key.starts_with(prefix))
+      })
+      .collect()
+  }
+
+  pub fn get_fees(&self, exchange: &str) -> Fees {
+    self.fees.get(exchange).cloned().unwrap_or_default()
+  }
+
+  fn validate(&self) -> Result<(), ConfigError> {
+    let invalid = |field, reason: &str| Err(ConfigError::Invalid(field, reason.to_string()));
+
+    if self.port == 0 {
+      return invalid("port", "must not be 0");
+    }
+
+    if self.signal_threshold <= 0.0 {
+      return invalid("signal_threshold", "must be positive");
+    }
+
+    if !(0.0..=self.signal_threshold).contains(&self.signal_exit_threshold) {
+      return invalid(
+        "signal_exit_threshold",
+        "must be between 0 and signal_threshold",
+      );
+    }
+
+    if self.exchanges.is_empty() {
+      return invalid("exchanges", "must not be empty");
+    }
+
+    if self.reconnect_initial_delay_ms > self.reconnect_max_delay_ms {
+      return invalid(
+        "reconnect_initial_delay_ms",
+        "must not exceed reconnect_max_delay_ms",
+      );
+    }
+
+    if self.reconnect_multiplier < 1.0 {
+      return invalid("reconnect_multiplier", "must be at least 1");
+    }
+
+    if !(0.0..=1.0).contains(&self.reconnect_jitter) {
+      return invalid("reconnect_jitter", "must be between 0 and 1");
+    }
+
+    if self.feed_idle_timeout_ms == 0 {
+      return invalid("feed_idle_timeout_ms", "must not be 0");
+    }
+
+    if self.vwap_window_ms <= 0 {
+      return invalid("vwap_window_ms", "must be positive");
+    }
+
+    if self.triangle_max_quote_age_ms <= 0 {
+      return invalid("triangle_max_quote_age_ms", "must be positive");
+    }
+
+    if !(1..=5000).contains(&self.order_book_depth) {
+      return invalid("order_book_depth", "must be between 1 and 5000");
+    }
+
+    if !(1..=1024).contains(&self.binance_max_streams_per_connection) {
+      return invalid(
+        "binance_max_streams_per_connection",
+        "must be between 1 and 1024",
+      );
+    }
+
+    if !["trade", "aggTrade"].contains(&self.binance_trade_stream.as_str()) {
+      return invalid("binance_trade_stream", "must be trade or aggTrade");
+    }
+
+    if self.bitkub_depth_poll_interval_ms == 0 {
+      return invalid("bitkub_depth_poll_interval_ms", "must not be 0");
+    }
+
+    if self.fees.values().any(|fees| {
+      !(0.0..1.0).contains(&fees.maker)
+        || !(0.0..1.0).contains(&fees.taker)
+        || fees.withdrawal.values().any(|fee| *fee < 0.0)
+    }) {
+      return invalid(
+        "fees",
+        "trading fees must be between 0 and 1, withdrawal fees not negative",
+      );
+    }
+
+    if self.net_rate_notional <= 0.0 {
+      return invalid("net_rate_notional", "must be positive");
+    }
+
+    if self.network_status_poll_interval_ms == 0 {
+      return invalid("network_status_poll_interval_ms", "must not be 0");
+    }
+
+    for (field, key, secret) in [
+      (
+        "binance_api_key",
+        &self.binance_api_key,
+        &self.binance_api_secret,
+      ),
+      (
+        "bitkub_api_key",
+        &self.bitkub_api_key,
+        &self.bitkub_api_secret,
+      ),
+      (
+        "kraken_api_key",
+        &self.kraken_api_key,
+        &self.kraken_api_secret,
+      ),
+      ("okx_api_key", &self.okx_api_key, &self.okx_api_secret),
+      ("bybit_api_key", &self.bybit_api_key, &self.bybit_api_secret),
+    ] {
+      if key.is_empty() != secret.is_empty() {
+        let reason = format!(
+          "must be set together with {}",
+          field.replace("_key", "_secret")
+        );
+        return invalid(field, &reason);
+      }
+    }
+
+    if self.okx_api_key.is_empty() != self.okx_api_passphrase.is_empty() {
+      return invalid(
+        "okx_api_key",
+        "must be set together with okx_api_passphrase",
+      );
+    }
+
+    if self.fx && self.fx_url.is_empty() {
+      return invalid("fx_url", "must be set when fx is on");
+    }
+
+    if self.fx_poll_interval_ms == 0 {
+      return invalid("fx_poll_interval_ms", "must not be 0");
+    }
+
+    if self.fx_max_age_ms <= 0 {
+      return invalid("fx_max_age_ms", "must be positive");
+    }
+
+    Ok(())
+  }
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Self {
+      port: crate::PORT,
+      logging_level: crate::DEFAULT_LOGGING_LEVEL.to_string(),
+      signal_threshold: crate::SIGNAL_THRESHOLD,
+      signal_exit_threshold: crate::SIGNAL_EXIT_THRESHOLD,
+      signal_persistence_ms: crate::SIGNAL_PERSISTENCE_MS,
+      signal_cooldown_ms: crate::SIGNAL_COOLDOWN_MS,
+      symbols: split_list(crate::SYMBOLS),
+      symbol_discovery: crate::SYMBOL_DISCOVERY,
+      aliases: String::new(),
+      exchanges: split_list(crate::EXCHANGES),
+      listings: BTreeMap::new(),
+      reconnect_initial_delay_ms: crate::RECONNECT_INITIAL_DELAY_MS,
+      reconnect_max_delay_ms: crate::RECONNECT_MAX_DELAY_MS,
+      reconnect_multiplier: crate::RECONNECT_MULTIPLIER,
+      reconnect_jitter: crate::RECONNECT_JITTER,
+      reconnect_max_attempts: crate::RECONNECT_MAX_ATTEMPTS,
+      feed_idle_timeout_ms: crate::FEED_IDLE_TIMEOUT_MS,
+      order_books: crate::ORDER_BOOKS,
+      order_book_depth: crate::ORDER_BOOK_DEPTH,
+      trades: crate::TRADES,
+      vwap_window_ms: crate::VWAP_WINDOW_MS,
+      binance_max_streams_per_connection: crate::BINANCE_MAX_STREAMS_PER_CONNECTION,
+      binance_subscribe_over_socket: crate::BINANCE_SUBSCRIBE_OVER_SOCKET,
+      binance_book_ticker: crate::BINANCE_BOOK_TICKER,
+      binance_trade_stream: crate::BINANCE_TRADE_STREAM.to_string(),
+      binance_triangle_quotes: split_list(crate::BINANCE_TRIANGLE_QUOTES),
+      triangle_max_quote_age_ms: crate::TRIANGLE_MAX_QUOTE_AGE_MS,
+      bitkub_depth_poll_interval_ms: crate::BITKUB_DEPTH_POLL_INTERVAL_MS,
+      fees: Fees::get_defaults(),
+      net_rate_notional: crate::NET_RATE_NOTIONAL,
+      network_status: crate::NETWORK_STATUS,
+      network_status_poll_interval_ms: crate::NETWORK_STATUS_POLL_INTERVAL_MS,
+      binance_api_key: String::new(),
+      binance_api_secret: String::new(),
+      bitkub_api_key: String::new(),
+      bitkub_api_secret: String::new(),
+      kraken_api_key: String::new(),
+      kraken_api_secret: String::new(),
+      okx_api_key: String::new(),
+      okx_api_secret: String::new(),
+      okx_api_passphrase: String::new(),
+      bybit_api_key: String::new(),
+      bybit_api_secret: String::new(),
+      fx: crate::FX,
+      fx_url: crate::FX_URL.to_string(),
+      fx_poll_interval_ms: crate::FX_POLL_INTERVAL_MS,
+      fx_max_age_ms: crate::FX_MAX_AGE_MS,
+    }
+  }
+}
+
+impl Display for ConfigError {
+  fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    match self {
+      Self::Read(path, err) => write!(f, "cannot read {path}: {err}"),
+      Self::Parse(err) => write!(f, "invalid configuration: {err}"),
+      Self::Aliases(path, err) => write!(f, "invalid aliases in {path}: {err}"),
+      Self::Invalid(field, reason) => write!(f, "invalid `{field}`: {reason}"),
+    }
+  }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn split_list(list: &str) -> Vec<String> {
+  list
+    .split(',')
+    .map(str::trim)
+    .filter(|s| !s.is_empty())
+    .map(String::from)
+    .collect()
+}
+
+fn get_list_changes(name: &str, old: &[String], new: &[String], changes: &mut Vec<String>) {
+  let added = new
+    .iter()
+    .filter(|s| !old.contains(s))
+    .map(|s| format!("+{s}"));
+  let removed = old
+    .iter()
+    .filter(|s| !new.contains(s))
+    .map(|s| format!("-{s}"));
+  let diff = added.chain(removed).collect::<Vec<_>>();
+
+  if !diff.is_empty() {
+    changes.push(format!("{name}: {}", diff.join(", ")));
+  }
+}
+
+fn flatten_table(prefix: &str, table: Table, values: &mut BTreeMap<String, Value>) {
+  table.into_iter().for_each(|(key, value)| {
+    let key = format!("{prefix}{key}");
+
+    match value {
+      Value::Table(table) => flatten_table(&format!("{key}."), table, values),
+      value => {
+        values.insert(key, value);
+      }
+    }
+  });
+}
+
+fn merge_table(table: &mut Table, overrides: Table) {
+  overrides
+    .into_iter()
+    .for_each(|(key, value)| match (table.get_mut(&key), value) {
+      (Some(Value::Table(table)), Value::Table(overrides)) => merge_table(table, overrides),
+      (_, value) => {
+        table.insert(key, value);
+      }
+    });
+}
+
+fn parse_env_value(value: String) -> Value {
+  match format!("value = {value}").parse::<Table>() {
+    Ok(mut table) => table.remove("value").unwrap_or(Value::String(value)),
+    Err(_) => Value::String(value),
+  }
+}
+
+fn deserialize_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum List {
+    Comma(String),
+    Items(Vec<String>),
+  }
+
+  Ok(match List::deserialize(deserializer)? {
+    List::Comma(list) => split_list(&list),
+    List::Items(items) => items,
+  })
+}
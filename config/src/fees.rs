@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Fees {
+  pub maker: f32,
+  pub taker: f32,
+  pub withdrawal: BTreeMap<String, f32>,
+}
+
+impl Fees {
+  pub fn get_defaults() -> BTreeMap<String, Self> {
+    toml::from_str(include_str!("../fees.toml"))
+      .unwrap_or_else(|err| panic!("invalid fees.toml: {err}"))
+  }
+
+  pub fn get_withdrawal(&self, symbol: &str) -> Option<f32> {
+    self.withdrawal.get(symbol).copied()
+  }
+}
@@ -1,5 +1,41 @@
+pub mod aliases;
+mod config;
+pub mod fees;
+
+pub use self::config::{Config, ConfigError};
+
 pub const PORT: u16 = 3000;
 pub const DEFAULT_LOGGING_LEVEL: &str = "info";
 pub const SIGNAL_THRESHOLD: f32 = 0.025;
+pub const SIGNAL_EXIT_THRESHOLD: f32 = 0.015;
+pub const SIGNAL_PERSISTENCE_MS: u64 = 3_000;
+pub const SIGNAL_COOLDOWN_MS: u64 = 300_000;
 pub const CURRENCY_SYMBOL: &str = "btc";
-pub const SYMBOLS: &str = "btc,eth,wan,ada,omg,bch,usdt,ltc,xrp,zil,snt,cvc,link,iost,zrx,knc,abt,mana,ctxc,xlm,six,jfin,bnb,pow,doge,trx,dai,xtz,usdc,bat,mkr,enj,band,comp,ksm,dot,near,scrt,glm,don,yfi,uni,aave,alpha,ocean,snx,sand,bal,crv,grt,matic,kub,axs,sushi,ftt,imx,dydx,ens,boba,ilv,gala,gt,sgb,lyxe,chz,gf,exfi,sol,avax,ftm,luna,ape,hbar,lrc,celo,gal,solo,luna2,op";
+pub const STABLECOIN_SYMBOL: &str = "usdt";
+pub const SYMBOLS: &str = "btc,eth,wan,ada,omg,bch,usdt,ltc,xrp,zil,snt,cvc,link,iost,zrx,knc,abt,mana,ctxc,xlm,six,jfin,bnb,powr,doge,trx,dai,xtz,usdc,bat,mkr,enj,band,comp,ksm,dot,near,scrt,glm,don,yfi,uni,aave,alpha,ocean,snx,sand,bal,crv,grt,matic,kub,axs,sushi,ftt,imx,dydx,ens,boba,ilv,gala,gt,sgb,lyxe,chz,gf,exfi,sol,avax,ftm,luna,ape,hbar,lrc,celo,gal,solo,luna2,op";
+pub const RECONNECT_INITIAL_DELAY_MS: u64 = 500;
+pub const RECONNECT_MAX_DELAY_MS: u64 = 60_000;
+pub const RECONNECT_MULTIPLIER: f64 = 2.0;
+pub const RECONNECT_JITTER: f64 = 0.2;
+pub const RECONNECT_MAX_ATTEMPTS: Option<u32> = None;
+pub const FEED_IDLE_TIMEOUT_MS: u64 = 30_000;
+pub const EXCHANGES: &str = "binance,bitkub,kraken,coinbase,okx,bybit";
+pub const BINANCE_MAX_STREAMS_PER_CONNECTION: usize = 200;
+pub const BINANCE_SUBSCRIBE_OVER_SOCKET: bool = false;
+pub const BINANCE_BOOK_TICKER: bool = false;
+pub const ORDER_BOOKS: bool = true;
+pub const ORDER_BOOK_DEPTH: usize = 100;
+pub const BITKUB_DEPTH_POLL_INTERVAL_MS: u64 = 5_000;
+pub const TRADES: bool = true;
+pub const BINANCE_TRADE_STREAM: &str = "aggTrade";
+pub const BINANCE_TRIANGLE_QUOTES: &str = "btc,eth";
+pub const TRIANGLE_MAX_QUOTE_AGE_MS: i64 = 10_000;
+pub const VWAP_WINDOW_MS: i64 = 300_000;
+pub const SYMBOL_DISCOVERY: bool = true;
+pub const NET_RATE_NOTIONAL: f32 = 0.1;
+pub const NETWORK_STATUS: bool = true;
+pub const NETWORK_STATUS_POLL_INTERVAL_MS: u64 = 60_000;
+pub const FX: bool = false;
+pub const FX_URL: &str = "";
+pub const FX_POLL_INTERVAL_MS: u64 = 300_000;
+pub const FX_MAX_AGE_MS: i64 = 900_000;
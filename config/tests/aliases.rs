@@ -0,0 +1,79 @@
+use config::aliases::Aliases;
+use config::ConfigError;
+use std::env::temp_dir;
+use std::fs::write;
+
+const ALIASES: &str = r#"
+[assets.luna]
+binance = "lunc"
+
+[assets.powr]
+bitkub = "pow"
+
+[inbound.kraken]
+xbt = "btc"
+
+[networks.bitkub]
+erc20 = "eth"
+"#;
+
+#[test]
+fn maps_assets_to_venue_tickers_and_back() {
+  let aliases = Aliases::parse(ALIASES).unwrap();
+
+  assert_eq!(aliases.get_ticker("binance", "luna"), "lunc");
+  assert_eq!(aliases.get_ticker("kraken", "luna"), "luna");
+  assert_eq!(aliases.get_key("binance", "lunc"), "luna");
+  assert_eq!(aliases.get_key("bitkub", "pow"), "powr");
+  assert_eq!(aliases.get_key("kraken", "xbt"), "btc");
+  assert_eq!(aliases.get_ticker("kraken", "btc"), "btc");
+  assert_eq!(aliases.get_key("okx", "eth"), "eth");
+}
+
+#[test]
+fn maps_network_names_case_insensitively() {
+  let aliases = Aliases::parse(ALIASES).unwrap();
+
+  assert_eq!(aliases.get_network("bitkub", "ERC20"), "eth");
+  assert_eq!(aliases.get_network("bitkub", "TRC20"), "trc20");
+  assert_eq!(aliases.get_network("binance", "ERC20"), "erc20");
+}
+
+#[test]
+fn overrides_the_built_in_aliases_from_a_file() {
+  let path = temp_dir().join(format!("aliases-{}.toml", std::process::id()));
+  write(&path, "[assets.luna]\nokx = \"terra\"").unwrap();
+  let aliases = Aliases::load(path.to_str().unwrap()).unwrap();
+  let builtin = Aliases::load("").unwrap();
+
+  assert_eq!(aliases.get_ticker("okx", "luna"), "terra");
+  assert_eq!(aliases.get_ticker("binance", "luna"), "luna");
+  assert_eq!(builtin.get_ticker("binance", "luna"), "lunc");
+  assert_eq!(builtin.get_network("binance", "ARBITRUM"), "arb");
+}
+
+#[test]
+fn replaces_the_installed_aliases() {
+  Aliases::set(Aliases::parse("[assets.luna]\nokx = \"terra\"").unwrap());
+  assert_eq!(Aliases::get().get_ticker("okx", "luna"), "terra");
+  assert_eq!(Aliases::get().get_ticker("binance", "luna"), "luna");
+
+  Aliases::set(Aliases::load("").unwrap());
+  assert_eq!(Aliases::get().get_ticker("okx", "luna"), "lunc");
+  assert_eq!(Aliases::get().get_ticker("binance", "luna"), "lunc");
+}
+
+#[test]
+fn reports_missing_and_malformed_files() {
+  let path = temp_dir().join(format!("aliases-invalid-{}.toml", std::process::id()));
+  write(&path, "[assets.luna]\nbinance = 1").unwrap();
+
+  assert!(matches!(
+    Aliases::load(path.with_extension("missing").to_str().unwrap()),
+    Err(ConfigError::Read(_, _))
+  ));
+  assert!(matches!(
+    Aliases::load(path.to_str().unwrap()),
+    Err(ConfigError::Aliases(_, _))
+  ));
+}
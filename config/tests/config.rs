@@ -0,0 +1,187 @@
+use config::{Config, ConfigError};
+use std::env::{set_var, temp_dir};
+use std::fs::write;
+
+fn get_vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+  vars
+    .iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect()
+}
+
+fn get_invalid(file: &str) -> &'static str {
+  match Config::from_sources(file, Vec::new()) {
+    Err(ConfigError::Invalid(field, _)) => field,
+    config => panic!("unexpected {config:?}"),
+  }
+}
+
+#[test]
+fn reads_values_from_the_file() {
+  let file = "port = 3001\nsymbols = [\"eth\", \"xrp\"]\nexchanges = \"binance, kraken\"";
+  let config = Config::from_sources(file, Vec::new()).unwrap();
+
+  assert_eq!(config.port, 3001);
+  assert_eq!(config.symbols, vec!["eth", "xrp"]);
+  assert_eq!(config.exchanges, vec!["binance", "kraken"]);
+  assert_eq!(config.logging_level, Config::default().logging_level);
+}
+
+#[test]
+fn overrides_the_file_with_env_vars() {
+  let vars = get_vars(&[
+    ("HODLER_PORT", "3002"),
+    ("HODLER_SYMBOLS", "btc,eth"),
+    ("HODLER_SIGNAL_THRESHOLD", "0.03"),
+    ("HODLER_RECONNECT_MAX_ATTEMPTS", "5"),
+    ("HODLER_FEES", "{ binance = { taker = 0.002 } }"),
+  ]);
+  let config = Config::from_sources("port = 3001", vars).unwrap();
+
+  assert_eq!(config.port, 3002);
+  assert_eq!(config.symbols, vec!["btc", "eth"]);
+  assert_eq!(config.signal_threshold, 0.03);
+  assert_eq!(config.reconnect_max_attempts, Some(5));
+  assert_eq!(config.get_fees("binance").taker, 0.002);
+  assert_eq!(
+    config.get_fees("binance").maker,
+    Config::default().get_fees("binance").maker
+  );
+}
+
+#[test]
+fn ignores_unrelated_env_vars() {
+  let vars = get_vars(&[
+    ("HODLER_LOG", "debug"),
+    ("HODLER_CONFIG", "elsewhere.toml"),
+    ("PORT", "0"),
+  ]);
+
+  assert_eq!(Config::from_sources("", vars).unwrap(), Config::default());
+}
+
+#[test]
+fn keeps_numeric_looking_strings() {
+  let vars = get_vars(&[
+    ("HODLER_BITKUB_API_KEY", "1234567890"),
+    ("HODLER_BITKUB_API_SECRET", "1.50"),
+    ("HODLER_LOGGING_LEVEL", "true"),
+  ]);
+  let config = Config::from_sources("", vars).unwrap();
+
+  assert_eq!(config.bitkub_api_key, "1234567890");
+  assert_eq!(config.bitkub_api_secret, "1.50");
+  assert_eq!(config.logging_level, "true");
+}
+
+#[test]
+fn rejects_unknown_keys_and_wrong_types_in_the_file() {
+  assert!(matches!(
+    Config::from_sources("log = \"debug\"", Vec::new()),
+    Err(ConfigError::Parse(_))
+  ));
+  assert!(matches!(
+    Config::from_sources("port = \"high\"", Vec::new()),
+    Err(ConfigError::Parse(_))
+  ));
+  assert!(matches!(
+    Config::from_sources("port = ", Vec::new()),
+    Err(ConfigError::Parse(_))
+  ));
+}
+
+#[test]
+fn validates_values() {
+  assert_eq!(get_invalid("port = 0"), "port");
+  assert_eq!(get_invalid("signal_threshold = 0.0"), "signal_threshold");
+  assert_eq!(
+    get_invalid("signal_exit_threshold = 0.05"),
+    "signal_exit_threshold"
+  );
+  assert_eq!(get_invalid("exchanges = []"), "exchanges");
+  assert_eq!(
+    get_invalid("reconnect_initial_delay_ms = 120000"),
+    "reconnect_initial_delay_ms"
+  );
+  assert_eq!(get_invalid("order_book_depth = 0"), "order_book_depth");
+  assert_eq!(
+    get_invalid("binance_trade_stream = \"kline\""),
+    "binance_trade_stream"
+  );
+  assert_eq!(get_invalid("fees.binance.taker = 1.5"), "fees");
+  assert_eq!(get_invalid("bitkub_api_key = \"key\""), "bitkub_api_key");
+  assert_eq!(get_invalid("fx = true"), "fx_url");
+  assert!(Config::from_sources("symbols = [\"eth\"]", Vec::new()).is_ok());
+}
+
+#[test]
+fn loads_the_file_named_by_hodler_config() {
+  let path = temp_dir().join(format!("hodler-{}.toml", std::process::id()));
+  write(&path, "port = 3003").unwrap();
+  set_var("HODLER_CONFIG", &path);
+
+  assert_eq!(Config::load().unwrap().port, 3003);
+
+  set_var("HODLER_CONFIG", path.with_extension("missing"));
+
+  assert!(matches!(Config::load(), Err(ConfigError::Read(_, _))));
+
+  write(&path, "aliases = \"missing-aliases.toml\"").unwrap();
+  set_var("HODLER_CONFIG", &path);
+
+  assert!(matches!(
+    Config::load(),
+    Err(ConfigError::Read(aliases, _)) if aliases == temp_dir().join("missing-aliases.toml").to_string_lossy()
+  ));
+}
+
+#[test]
+fn lists_changes_between_configs() {
+  let old = Config::from_sources("symbols = [\"btc\", \"eth\", \"xrp\"]", Vec::new()).unwrap();
+  let new = Config::from_sources(
+    "symbols = [\"btc\", \"eth\", \"sol\"]\nport = 3001\nbitkub_api_key = \"key\"\nbitkub_api_secret = \"secret\"\nokx_api_key = \"key\"\nokx_api_secret = \"secret\"\nokx_api_passphrase = \"passphrase\"\nfees.okx.taker = 0.002",
+    Vec::new(),
+  )
+  .unwrap();
+
+  assert_eq!(
+    old.get_changes(&new),
+    vec![
+      "symbols: +sol, -xrp",
+      "bitkub_api_key: <redacted> -> <redacted>",
+      "bitkub_api_secret: <redacted> -> <redacted>",
+      "fees.okx.taker: 0.001 -> 0.002",
+      "okx_api_key: <redacted> -> <redacted>",
+      "okx_api_passphrase: <redacted> -> <redacted>",
+      "okx_api_secret: <redacted> -> <redacted>",
+      "port: 3000 -> 3001",
+    ]
+  );
+  assert!(new.get_changes(&new).is_empty());
+}
+
+#[test]
+fn lists_feed_changes_per_venue() {
+  let old = Config::default();
+  let new = Config::from_sources(
+    "reconnect_max_delay_ms = 30000\nfeed_idle_timeout_ms = 10000\nbitkub_depth_poll_interval_ms = 1000\norder_book_depth = 50\nbinance_api_key = \"key\"\nbinance_api_secret = \"secret\"\nfees.okx.taker = 0.002\nport = 3001",
+    Vec::new(),
+  )
+  .unwrap();
+  let shared = [
+    "feed_idle_timeout_ms: 30000 -> 10000",
+    "order_book_depth: 100 -> 50",
+    "reconnect_max_delay_ms: 60000 -> 30000",
+  ];
+
+  assert_eq!(
+    old.get_feed_changes(&new, "bitkub"),
+    [
+      &["bitkub_depth_poll_interval_ms: 5000 -> 1000"],
+      &shared[..]
+    ]
+    .concat()
+  );
+  assert_eq!(old.get_feed_changes(&new, "binance"), shared);
+  assert_eq!(old.get_feed_changes(&new, "okx"), shared);
+}
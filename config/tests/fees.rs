@@ -0,0 +1,47 @@
+use config::fees::Fees;
+use config::Config;
+
+#[test]
+fn loads_default_fees_for_every_venue() {
+  let defaults = Fees::get_defaults();
+
+  for venue in config::EXCHANGES.split(',') {
+    let fees = &defaults[venue];
+
+    assert!(fees.taker > 0.0 && fees.taker < 1.0, "{venue}");
+    assert!(fees.maker >= 0.0 && fees.maker <= fees.taker, "{venue}");
+  }
+  assert_eq!(defaults["binance"].get_withdrawal("eth"), Some(0.0016));
+}
+
+#[test]
+fn reports_missing_withdrawal_fees() {
+  let config = Config::default();
+
+  assert_eq!(config.get_fees("binance").get_withdrawal("unknown"), None);
+  assert_eq!(config.get_fees("coinbase").get_withdrawal("eth"), None);
+  assert_eq!(config.get_fees("unknown"), Fees::default());
+}
+
+#[test]
+fn merges_file_overrides_per_venue_and_asset() {
+  let file = "[fees.kraken]\nwithdrawal = { eth = 0.01 }\n\n[fees.newex]\ntaker = 0.003";
+  let config = Config::from_sources(file, Vec::new()).unwrap();
+  let defaults = Fees::get_defaults();
+
+  assert_eq!(config.get_fees("kraken").withdrawal["eth"], 0.01);
+  assert_eq!(
+    config.get_fees("kraken").withdrawal["btc"],
+    defaults["kraken"].withdrawal["btc"]
+  );
+  assert_eq!(config.get_fees("kraken").taker, defaults["kraken"].taker);
+  assert_eq!(config.get_fees("binance"), defaults["binance"]);
+  assert_eq!(config.get_fees("newex").taker, 0.003);
+  assert!(config.get_fees("newex").withdrawal.is_empty());
+}
+
+#[test]
+fn rejects_invalid_fees() {
+  assert!(Config::from_sources("[fees.binance]\nrebate = 0.1", Vec::new()).is_err());
+  assert!(Config::from_sources("[fees.binance]\nwithdrawal = { eth = -1.0 }", Vec::new()).is_err());
+}
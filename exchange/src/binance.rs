@@ -1,29 +1,369 @@
+pub mod depth;
 pub mod ticker;
+pub mod trade;
 
+use crate::client::{ExchangeClient, Shard};
+use crate::error::ParseError;
+use crate::event::MarketEvent;
 use crate::exchange::Exchange;
-use futures_util::stream::SplitStream;
-use futures_util::StreamExt;
-use tokio::net::TcpStream;
-use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use crate::rest::get_text;
+use crate::supervisor::Backoff;
+use async_trait::async_trait;
+use chrono::Utc;
+use config::aliases::Aliases;
+use config::Config;
+use depth::{BinanceDepth, BinanceDepthSnapshot, Depth, DepthSnapshot, DepthUpdate};
+use futures_util::future::join_all;
+use hodler::models::book::OrderBook;
+use hodler::models::ticker::MarketTicker;
+use hodler::models::trade::MarketTrade;
+use hodler::models::triangle::MarketPair;
+use log::warn;
+use serde_json::{from_str, json};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use ticker::{BinanceBookTicker, BinanceFrame, BinanceResponse, BinanceTicker, BookTicker, Ticker};
+use trade::{BinanceTrade, Trade};
+
+const SOCKET_URL: &str = "wss://stream.binance.com:9443/stream";
 
 #[derive(Clone)]
 pub struct Client {
   pub name: Exchange,
-  ticker_url: String,
+  shards: Vec<Shard>,
+  order_books: bool,
+  order_book_depth: usize,
+  rest_url: String,
+  http: reqwest::Client,
+  depths: Arc<Mutex<HashMap<String, Depth>>>,
+  backoff: Backoff,
+  retries: Arc<Mutex<HashMap<String, (u32, Instant)>>>,
+  book_tickers: Arc<Mutex<HashMap<String, BookTicker>>>,
+  tickers: Arc<Mutex<HashMap<String, MarketTicker>>>,
+  pairs: HashMap<String, (String, String)>,
 }
 
 impl Client {
   pub fn new() -> Self {
+    Self::with_config(&Config::default())
+  }
+
+  pub fn with_config(config: &Config) -> Self {
+    let name = Exchange::BinanceEx;
+    let trade_stream = format!("@{}", config.binance_trade_stream);
+    let pairs = get_pairs(&name, config);
+    let streams = name
+      .get_tickers(&name.get_symbols(config))
+      .into_iter()
+      .flat_map(|ticker| {
+        let book_ticker = config
+          .binance_book_ticker
+          .then(|| ticker.replace("@ticker", "@bookTicker"));
+        let depth = config
+          .order_books
+          .then(|| ticker.replace("@ticker", "@depth"));
+        let trade = config
+          .trades
+          .then(|| ticker.replace("@ticker", &trade_stream));
+
+        [book_ticker, depth, trade, Some(ticker)]
+          .into_iter()
+          .flatten()
+      })
+      .chain(
+        pairs
+          .iter()
+          .map(|(ticker_name, _)| format!("{ticker_name}@ticker")),
+      )
+      .collect::<Vec<String>>();
+    let shards = streams
+      .chunks(config.binance_max_streams_per_connection)
+      .enumerate()
+      .map(|(id, tickers)| match config.binance_subscribe_over_socket {
+        true => Shard {
+          ticker_url: SOCKET_URL.to_string(),
+          subscriptions: vec![json!({
+            "method": "SUBSCRIBE",
+            "params": tickers,
+            "id": id + 1,
+          })
+          .to_string()],
+        },
+        false => Shard {
+          ticker_url: name.get_ticker_url_for(tickers),
+          subscriptions: Vec::new(),
+        },
+      })
+      .collect();
+
     Self {
-      name: Exchange::BinanceEx,
-      ticker_url: Exchange::BinanceEx.get_ticker_url(),
+      rest_url: name.get_rest_url(),
+      name,
+      shards,
+      order_books: config.order_books,
+      order_book_depth: config.order_book_depth,
+      http: reqwest::Client::new(),
+      depths: Arc::new(Mutex::new(HashMap::new())),
+      backoff: Backoff::from(config),
+      retries: Arc::new(Mutex::new(HashMap::new())),
+      book_tickers: Arc::new(Mutex::new(HashMap::new())),
+      tickers: Arc::new(Mutex::new(HashMap::new())),
+      pairs: pairs.into_iter().collect(),
+    }
+  }
+
+  fn merge_ticker(&self, ticker: Ticker) -> MarketTicker {
+    let mut market_ticker = MarketTicker::from(ticker);
+
+    if let Some(book_ticker) = self
+      .book_tickers
+      .lock()
+      .unwrap()
+      .get(&market_ticker.ticker_name)
+    {
+      book_ticker.merge_into(&mut market_ticker);
+    }
+
+    self
+      .tickers
+      .lock()
+      .unwrap()
+      .insert(market_ticker.ticker_name.clone(), market_ticker.clone());
+
+    market_ticker
+  }
+
+  fn get_event(&self, ticker: Ticker) -> MarketEvent {
+    match self.pairs.get(&ticker.ticker_name) {
+      Some((base, quote)) => MarketPair {
+        exchange: self.name.get_name(),
+        base: base.clone(),
+        quote: quote.clone(),
+        ticker_name: ticker.ticker_name,
+        ask_price: ticker.ask_price,
+        bid_price: ticker.bid_price,
+        timestamp: ticker.timestamp,
+      }
+      .into(),
+      None => self.merge_ticker(ticker).into(),
     }
   }
 
-  pub async fn connect_ws(&self) -> SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>> {
-    let (stream, _) = connect_async(self.ticker_url.clone()).await.unwrap();
-    let (_, read) = stream.split();
+  fn merge_book_ticker(&self, book_ticker: BookTicker) -> Option<MarketTicker> {
+    let market_ticker = self
+      .tickers
+      .lock()
+      .unwrap()
+      .get(&book_ticker.ticker_name)
+      .map(|market_ticker| {
+        let mut market_ticker = market_ticker.clone();
+        book_ticker.merge_into(&mut market_ticker);
+        market_ticker.exchange_timestamp = None;
+        market_ticker.received_at = Utc::now().timestamp_millis();
+        market_ticker
+      });
+
+    self
+      .book_tickers
+      .lock()
+      .unwrap()
+      .insert(book_ticker.ticker_name.clone(), book_ticker);
+
+    market_ticker
+  }
 
-    return read;
+  fn merge_depth(&self, update: DepthUpdate) -> Option<OrderBook> {
+    self
+      .depths
+      .lock()
+      .unwrap()
+      .entry(update.ticker_name.clone())
+      .or_insert_with(|| Depth::Buffering(Vec::new()))
+      .apply(update, self.order_book_depth)
+  }
+
+  fn is_retry_due(&self, ticker_name: &str, now: Instant) -> bool {
+    match self.retries.lock().unwrap().get(ticker_name) {
+      Some((_, retry_at)) => *retry_at <= now,
+      None => true,
+    }
   }
+
+  fn schedule_retry(&self, ticker_name: &str, synced: bool) {
+    let mut retries = self.retries.lock().unwrap();
+
+    if synced {
+      retries.remove(ticker_name);
+      return;
+    }
+
+    let attempt = retries.get(ticker_name).map_or(0, |(attempt, _)| *attempt);
+    let retry_at = Instant::now() + self.backoff.get_delay(attempt);
+    retries.insert(ticker_name.to_string(), (attempt + 1, retry_at));
+  }
+
+  async fn sync_depth(&self, ticker_name: String) -> Option<Result<MarketEvent, ParseError>> {
+    let url = format!(
+      "{}/api/v3/depth?symbol={}&limit={}",
+      self.rest_url,
+      ticker_name.to_uppercase(),
+      self.order_book_depth
+    );
+    let text = match get_text(&self.http, &url).await {
+      Ok(text) => text,
+      Err(err) => {
+        warn!(target: &self.name.get_name(), "{err}");
+        return None;
+      }
+    };
+    let snapshot = match from_str::<BinanceDepthSnapshot>(&text)
+      .map_err(ParseError::from)
+      .and_then(DepthSnapshot::try_from)
+    {
+      Ok(snapshot) => snapshot,
+      Err(err) => return Some(Err(err)),
+    };
+
+    self
+      .depths
+      .lock()
+      .unwrap()
+      .get_mut(&ticker_name)?
+      .sync(ticker_name, snapshot, self.order_book_depth)
+      .map(|order_book| Ok(order_book.into()))
+  }
+}
+
+impl Default for Client {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[async_trait]
+impl ExchangeClient for Client {
+  fn name(&self) -> Exchange {
+    self.name.clone()
+  }
+
+  fn get_shards(&self) -> Vec<Shard> {
+    self.shards.clone()
+  }
+
+  fn decode(&self, text: &str) -> Vec<Result<MarketEvent, ParseError>> {
+    let stream = match from_str::<BinanceFrame>(text) {
+      Ok(BinanceFrame {
+        stream: Some(stream),
+      }) => stream,
+      Ok(BinanceFrame { stream: None }) => {
+        return match from_str::<BinanceResponse>(text) {
+          Ok(BinanceResponse { error: Some(_), .. }) => {
+            vec![Err(ParseError::SubscriptionRejected(text.to_string()))]
+          }
+          Ok(_) => vec![Err(ParseError::SubscriptionAck(text.to_string()))],
+          Err(err) => vec![Err(err.into())],
+        }
+      }
+      Err(err) => return vec![Err(err.into())],
+    };
+
+    match stream.rsplit('@').next() {
+      Some("bookTicker") => from_str::<BinanceBookTicker>(text)
+        .map_err(ParseError::from)
+        .and_then(BookTicker::try_from)
+        .map(|book_ticker| self.merge_book_ticker(book_ticker).map(MarketEvent::from))
+        .transpose()
+        .into_iter()
+        .collect(),
+      Some("depth") => from_str::<BinanceDepth>(text)
+        .map_err(ParseError::from)
+        .and_then(DepthUpdate::try_from)
+        .map(|update| self.merge_depth(update).map(MarketEvent::from))
+        .transpose()
+        .into_iter()
+        .collect(),
+      Some("trade" | "aggTrade") => vec![from_str::<BinanceTrade>(text)
+        .map_err(ParseError::from)
+        .and_then(Trade::try_from)
+        .map(|trade| MarketTrade::from(trade).into())],
+      _ => vec![from_str::<BinanceTicker>(text)
+        .map_err(ParseError::from)
+        .and_then(Ticker::try_from)
+        .map(|ticker| self.get_event(ticker))],
+    }
+  }
+
+  fn poll_interval(&self) -> Option<Duration> {
+    match self.order_books {
+      true => Some(Duration::from_secs(1)),
+      false => None,
+    }
+  }
+
+  async fn poll(&self) -> Vec<Result<MarketEvent, ParseError>> {
+    let now = Instant::now();
+    let ticker_names = self
+      .depths
+      .lock()
+      .unwrap()
+      .iter()
+      .filter(|(ticker_name, depth)| !depth.is_synced() && self.is_retry_due(ticker_name, now))
+      .map(|(ticker_name, _)| ticker_name.clone())
+      .collect::<Vec<_>>();
+    let snapshots = ticker_names.into_iter().map(|ticker_name| async move {
+      let event = self.sync_depth(ticker_name.clone()).await;
+      self.schedule_retry(&ticker_name, matches!(event, Some(Ok(_))));
+      event
+    });
+
+    join_all(snapshots).await.into_iter().flatten().collect()
+  }
+}
+
+// Without discovery every symbol is paired with each triangle quote; a pair
+// between two quotes is taken in their listed order (eth/btc for btc,eth).
+fn get_pairs(name: &Exchange, config: &Config) -> Vec<(String, (String, String))> {
+  let venue = name.get_name();
+  let local = name.get_quote().get_name();
+  let symbols = name.get_symbols(config);
+  let quotes = &config.binance_triangle_quotes;
+  let rank = |asset: &str| quotes.iter().position(|quote| quote == asset);
+  let get_ticker = |symbol: &str| Aliases::get().get_ticker(&venue, symbol);
+  let pairs = match config.listings.get(&venue) {
+    Some(listing) => listing
+      .iter()
+      .filter_map(|pair| pair.split_once('/'))
+      .map(|(symbol, quote)| (symbol.to_string(), quote.to_string()))
+      .collect(),
+    None if !config.symbol_discovery => symbols
+      .iter()
+      .flat_map(|symbol| {
+        quotes
+          .iter()
+          .map(move |quote| (symbol.clone(), quote.clone()))
+      })
+      .filter(|(symbol, quote)| {
+        symbol != quote && !matches!((rank(symbol), rank(quote)), (Some(s), Some(q)) if s < q)
+      })
+      .collect(),
+    None => {
+      if !quotes.is_empty() {
+        warn!(target: &venue, "no listing discovered, triangle pairs disabled");
+      }
+
+      Vec::new()
+    }
+  };
+
+  pairs
+    .into_iter()
+    .filter(|(symbol, quote)| {
+      *symbol != local && quotes.contains(quote) && symbols.contains(symbol)
+    })
+    .map(|(symbol, quote)| {
+      let ticker_name = format!("{}{}", get_ticker(&symbol), get_ticker(&quote));
+      (ticker_name, (symbol, quote))
+    })
+    .collect()
 }
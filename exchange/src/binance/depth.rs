@@ -0,0 +1,207 @@
+use crate::error::{parse_number, ParseError};
+use crate::exchange::Exchange;
+use chrono::Utc;
+use hodler::models::book::{Level, OrderBook};
+use log::warn;
+use serde::Deserialize;
+
+pub const MAX_BUFFERED_UPDATES: usize = 1_000;
+
+#[derive(Debug)]
+pub struct DepthUpdate {
+  pub ticker_name: String,
+  pub first_update_id: u64,
+  pub final_update_id: u64,
+  pub asks: Vec<Level>,
+  pub bids: Vec<Level>,
+  pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DepthSnapshot {
+  pub last_update_id: u64,
+  pub asks: Vec<Level>,
+  pub bids: Vec<Level>,
+}
+
+#[derive(Debug)]
+pub enum Depth {
+  Buffering(Vec<DepthUpdate>),
+  Synced {
+    order_book: OrderBook,
+    last_update_id: u64,
+  },
+}
+
+#[derive(Deserialize)]
+pub struct BinanceDepth {
+  stream: String,
+  data: DepthData,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct DepthData {
+  E: i64,
+  s: String,
+  U: u64,
+  u: u64,
+  a: Vec<[String; 2]>,
+  b: Vec<[String; 2]>,
+}
+
+#[derive(Deserialize)]
+pub struct BinanceDepthSnapshot {
+  #[serde(rename = "lastUpdateId")]
+  last_update_id: u64,
+  asks: Vec<[String; 2]>,
+  bids: Vec<[String; 2]>,
+}
+
+fn parse_levels(levels: Vec<[String; 2]>) -> Result<Vec<Level>, ParseError> {
+  levels
+    .into_iter()
+    .map(|[price, quantity]| {
+      Ok(Level {
+        price: parse_number("price", &price)?,
+        quantity: parse_number("quantity", &quantity)?,
+      })
+    })
+    .collect()
+}
+
+impl TryFrom<BinanceDepth> for DepthUpdate {
+  type Error = ParseError;
+
+  fn try_from(depth: BinanceDepth) -> Result<Self, Self::Error> {
+    if !depth.stream.ends_with("@depth") {
+      return Err(ParseError::UnknownStream(depth.stream));
+    }
+
+    let depth = depth.data;
+
+    Ok(DepthUpdate {
+      ticker_name: depth.s.to_lowercase(),
+      first_update_id: depth.U,
+      final_update_id: depth.u,
+      asks: parse_levels(depth.a)?,
+      bids: parse_levels(depth.b)?,
+      timestamp: depth.E,
+    })
+  }
+}
+
+impl TryFrom<BinanceDepthSnapshot> for DepthSnapshot {
+  type Error = ParseError;
+
+  fn try_from(snapshot: BinanceDepthSnapshot) -> Result<Self, Self::Error> {
+    Ok(DepthSnapshot {
+      last_update_id: snapshot.last_update_id,
+      asks: parse_levels(snapshot.asks)?,
+      bids: parse_levels(snapshot.bids)?,
+    })
+  }
+}
+
+impl Depth {
+  pub fn is_synced(&self) -> bool {
+    matches!(self, Self::Synced { .. })
+  }
+
+  pub fn apply(&mut self, update: DepthUpdate, depth: usize) -> Option<OrderBook> {
+    let (order_book, last_update_id) = match self {
+      Self::Buffering(updates) => {
+        if updates.len() >= MAX_BUFFERED_UPDATES {
+          updates.drain(..=updates.len() - MAX_BUFFERED_UPDATES);
+        }
+
+        updates.push(update);
+        return None;
+      }
+      Self::Synced {
+        order_book,
+        last_update_id,
+      } => (order_book, last_update_id),
+    };
+
+    if update.final_update_id <= *last_update_id {
+      return None;
+    }
+
+    if update.first_update_id > *last_update_id + 1 {
+      warn!(
+        target: &Exchange::BinanceEx.get_name(),
+        "{}: missed updates {}..{}, resyncing",
+        update.ticker_name,
+        *last_update_id + 1,
+        update.first_update_id
+      );
+      *self = Self::Buffering(vec![update]);
+      return None;
+    }
+
+    *last_update_id = update.final_update_id;
+    apply_update(order_book, update);
+
+    Some(order_book.truncate(depth))
+  }
+
+  pub fn sync(
+    &mut self,
+    ticker_name: String,
+    snapshot: DepthSnapshot,
+    depth: usize,
+  ) -> Option<OrderBook> {
+    let updates = match self {
+      Self::Buffering(updates) => std::mem::take(updates),
+      Self::Synced { .. } => return None,
+    };
+    let updates = updates
+      .into_iter()
+      .filter(|update| update.final_update_id > snapshot.last_update_id)
+      .collect::<Vec<_>>();
+
+    if let Some(update) = updates.first() {
+      if update.first_update_id > snapshot.last_update_id + 1 {
+        *self = Self::Buffering(updates);
+        return None;
+      }
+    }
+
+    let mut order_book = OrderBook {
+      asks: snapshot.asks,
+      bids: snapshot.bids,
+      timestamp: Utc::now().timestamp_millis(),
+      ..OrderBook::new(
+        Exchange::BinanceEx.get_name(),
+        Exchange::BinanceEx.get_key(ticker_name.clone()),
+        ticker_name,
+      )
+    };
+    let mut last_update_id = snapshot.last_update_id;
+
+    updates.into_iter().for_each(|update| {
+      last_update_id = update.final_update_id;
+      apply_update(&mut order_book, update);
+    });
+
+    *self = Self::Synced {
+      order_book: order_book.clone(),
+      last_update_id,
+    };
+
+    Some(order_book.truncate(depth))
+  }
+}
+
+fn apply_update(order_book: &mut OrderBook, update: DepthUpdate) {
+  update
+    .asks
+    .into_iter()
+    .for_each(|level| order_book.apply_ask(level.price, level.quantity));
+  update
+    .bids
+    .into_iter()
+    .for_each(|level| order_book.apply_bid(level.price, level.quantity));
+  order_book.timestamp = update.timestamp;
+}
@@ -1,6 +1,11 @@
+use crate::error::{parse_number, ParseError};
+use crate::exchange::Exchange;
+use chrono::Utc;
+use hodler::models::ticker::MarketTicker;
+use serde::de::Error;
 use serde::Deserialize;
 use serde::Deserializer;
-use std::str::FromStr;
+use serde_json::Value;
 
 #[derive(Debug)]
 pub struct Ticker {
@@ -12,8 +17,30 @@ pub struct Ticker {
   pub timestamp: i64,
 }
 
+#[derive(Clone, Debug)]
+pub struct BookTicker {
+  pub ask_price: f32,
+  pub ask_quantity: f32,
+  pub bid_price: f32,
+  pub bid_quantity: f32,
+  pub ticker_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct BinanceFrame {
+  pub stream: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BinanceResponse {
+  pub result: Option<Value>,
+  pub error: Option<Value>,
+  pub id: u64,
+}
+
 #[derive(Deserialize)]
-struct BinanceTicker {
+pub struct BinanceTicker {
+  stream: String,
   data: Data,
 }
 
@@ -28,20 +55,90 @@ struct Data {
   v: String,
 }
 
+#[derive(Deserialize)]
+pub struct BinanceBookTicker {
+  data: BookData,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct BookData {
+  a: String,
+  A: String,
+  b: String,
+  B: String,
+  s: String,
+}
+
+impl TryFrom<BinanceTicker> for Ticker {
+  type Error = ParseError;
+
+  fn try_from(ticker: BinanceTicker) -> Result<Self, Self::Error> {
+    if !ticker.stream.ends_with("@ticker") {
+      return Err(ParseError::UnknownStream(ticker.stream));
+    }
+
+    let ticker = ticker.data;
+
+    Ok(Ticker {
+      ask_price: parse_number("a", &ticker.a)?,
+      bid_price: parse_number("b", &ticker.b)?,
+      ticker_name: ticker.s.to_lowercase(),
+      change: parse_number("P", &ticker.P)?,
+      volume: parse_number("v", &ticker.v)?,
+      timestamp: ticker.E,
+    })
+  }
+}
+
+impl TryFrom<BinanceBookTicker> for BookTicker {
+  type Error = ParseError;
+
+  fn try_from(ticker: BinanceBookTicker) -> Result<Self, Self::Error> {
+    let ticker = ticker.data;
+
+    Ok(BookTicker {
+      ask_price: parse_number("a", &ticker.a)?,
+      ask_quantity: parse_number("A", &ticker.A)?,
+      bid_price: parse_number("b", &ticker.b)?,
+      bid_quantity: parse_number("B", &ticker.B)?,
+      ticker_name: ticker.s.to_lowercase(),
+    })
+  }
+}
+
 impl<'de> Deserialize<'de> for Ticker {
   fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
   where
     D: Deserializer<'de>,
   {
-    let ticker = BinanceTicker::deserialize(deserializer)?.data;
+    Ticker::try_from(BinanceTicker::deserialize(deserializer)?).map_err(D::Error::custom)
+  }
+}
 
-    Ok(Ticker {
-      ask_price: f32::from_str(&ticker.a).unwrap(),
-      bid_price: f32::from_str(&ticker.b).unwrap(),
-      ticker_name: ticker.s.to_lowercase(),
-      change: f32::from_str(&ticker.P).unwrap(),
-      volume: f32::from_str(&ticker.v).unwrap(),
-      timestamp: ticker.E,
-    })
+impl BookTicker {
+  pub fn merge_into(&self, market_ticker: &mut MarketTicker) {
+    market_ticker.ask_price = self.ask_price;
+    market_ticker.ask_quantity = Some(self.ask_quantity);
+    market_ticker.bid_price = self.bid_price;
+    market_ticker.bid_quantity = Some(self.bid_quantity);
+  }
+}
+
+impl From<Ticker> for MarketTicker {
+  fn from(ticker: Ticker) -> Self {
+    Self {
+      exchange: Exchange::BinanceEx.get_name(),
+      symbol: Exchange::BinanceEx.get_key(ticker.ticker_name.clone()),
+      ticker_name: ticker.ticker_name,
+      ask_price: ticker.ask_price,
+      ask_quantity: None,
+      bid_price: ticker.bid_price,
+      bid_quantity: None,
+      volume: Some(ticker.volume),
+      percent_change: Some(ticker.change),
+      exchange_timestamp: Some(ticker.timestamp),
+      received_at: Utc::now().timestamp_millis(),
+    }
   }
 }
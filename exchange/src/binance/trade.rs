@@ -0,0 +1,59 @@
+use crate::error::{parse_number, ParseError};
+use crate::exchange::Exchange;
+use hodler::models::trade::MarketTrade;
+use serde::Deserialize;
+
+#[derive(Debug)]
+pub struct Trade {
+  pub ticker_name: String,
+  pub price: f32,
+  pub quantity: f32,
+  pub timestamp: i64,
+}
+
+#[derive(Deserialize)]
+pub struct BinanceTrade {
+  stream: String,
+  data: Data,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct Data {
+  s: String,
+  p: String,
+  q: String,
+  T: i64,
+}
+
+impl TryFrom<BinanceTrade> for Trade {
+  type Error = ParseError;
+
+  fn try_from(trade: BinanceTrade) -> Result<Self, Self::Error> {
+    if !trade.stream.ends_with("@trade") && !trade.stream.ends_with("@aggTrade") {
+      return Err(ParseError::UnknownStream(trade.stream));
+    }
+
+    let trade = trade.data;
+
+    Ok(Trade {
+      ticker_name: trade.s.to_lowercase(),
+      price: parse_number("p", &trade.p)?,
+      quantity: parse_number("q", &trade.q)?,
+      timestamp: trade.T,
+    })
+  }
+}
+
+impl From<Trade> for MarketTrade {
+  fn from(trade: Trade) -> Self {
+    Self {
+      exchange: Exchange::BinanceEx.get_name(),
+      symbol: Exchange::BinanceEx.get_key(trade.ticker_name.clone()),
+      ticker_name: trade.ticker_name,
+      price: trade.price,
+      quantity: trade.quantity,
+      timestamp: trade.timestamp,
+    }
+  }
+}
@@ -1,29 +1,131 @@
+pub mod depth;
 pub mod ticker;
+pub mod trade;
 
+use crate::client::ExchangeClient;
+use crate::error::ParseError;
+use crate::event::MarketEvent;
 use crate::exchange::Exchange;
-use futures_util::stream::SplitStream;
-use futures_util::StreamExt;
-use tokio::net::TcpStream;
-use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use crate::rest::get_text;
+use async_trait::async_trait;
+use config::Config;
+use depth::Depth;
+use futures_util::future::join_all;
+use hodler::models::ticker::MarketTicker;
+use hodler::models::trade::MarketTrade;
+use log::warn;
+use serde_json::from_str;
+use std::time::Duration;
+use ticker::{BitkubFrame, Ticker};
+use trade::Trade;
 
 #[derive(Clone)]
 pub struct Client {
   pub name: Exchange,
   ticker_url: String,
+  tickers: Vec<String>,
+  order_book_depth: usize,
+  depth_poll_interval: Option<Duration>,
+  rest_url: String,
+  http: reqwest::Client,
 }
 
 impl Client {
   pub fn new() -> Self {
+    Self::with_config(&Config::default())
+  }
+
+  pub fn with_config(config: &Config) -> Self {
+    let name = Exchange::BitkubEx;
+    let tickers = name.get_tickers(&name.get_symbols(config));
+    let streams = tickers
+      .iter()
+      .flat_map(|ticker| {
+        let trade = config
+          .trades
+          .then(|| ticker.replace("market.ticker.", "market.trade."));
+
+        [Some(ticker.clone()), trade].into_iter().flatten()
+      })
+      .collect::<Vec<String>>();
+
     Self {
-      name: Exchange::BitkubEx.clone(),
-      ticker_url: Exchange::BitkubEx.get_ticker_url(),
+      ticker_url: name.get_ticker_url_for(&streams),
+      rest_url: name.get_rest_url(),
+      tickers,
+      order_book_depth: config.order_book_depth,
+      depth_poll_interval: config
+        .order_books
+        .then(|| Duration::from_millis(config.bitkub_depth_poll_interval_ms)),
+      name,
+      http: reqwest::Client::new(),
     }
   }
 
-  pub async fn connect_ws(&self) -> SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>> {
-    let (stream, _) = connect_async(self.ticker_url.clone()).await.unwrap();
-    let (_, read) = stream.split();
+  async fn get_order_book(&self, ticker_name: String) -> Option<Result<MarketEvent, ParseError>> {
+    let url = format!(
+      "{}/api/market/depth?sym={}&lmt={}",
+      self.rest_url,
+      ticker_name.to_uppercase(),
+      self.order_book_depth
+    );
+
+    match get_text(&self.http, &url).await {
+      Ok(text) => Some(
+        from_str::<Depth>(&text)
+          .map(|depth| depth.into_order_book(ticker_name).into())
+          .map_err(ParseError::from),
+      ),
+      Err(err) => {
+        warn!(target: &self.name.get_name(), "{err}");
+        None
+      }
+    }
+  }
+}
+
+impl Default for Client {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[async_trait]
+impl ExchangeClient for Client {
+  fn name(&self) -> Exchange {
+    self.name.clone()
+  }
+
+  fn ticker_url(&self) -> String {
+    self.ticker_url.clone()
+  }
+
+  fn decode(&self, text: &str) -> Vec<Result<MarketEvent, ParseError>> {
+    text
+      .split('\n')
+      .filter(|s| !s.trim().is_empty())
+      .map(|s| {
+        let frame = from_str::<BitkubFrame>(s)?;
+
+        match frame.stream.split('.').nth(1) {
+          Some("ticker") => Ok(MarketTicker::from(from_str::<Ticker>(s)?).into()),
+          Some("trade") => Ok(MarketTrade::from(from_str::<Trade>(s)?).into()),
+          _ => Err(ParseError::UnknownStream(frame.stream)),
+        }
+      })
+      .collect()
+  }
+
+  fn poll_interval(&self) -> Option<Duration> {
+    self.depth_poll_interval
+  }
+
+  async fn poll(&self) -> Vec<Result<MarketEvent, ParseError>> {
+    let order_books = self
+      .tickers
+      .iter()
+      .map(|ticker| self.get_order_book(ticker.replace("market.ticker.", "")));
 
-    return read;
+    join_all(order_books).await.into_iter().flatten().collect()
   }
 }
@@ -0,0 +1,32 @@
+use crate::exchange::Exchange;
+use chrono::Utc;
+use hodler::models::book::{Level, OrderBook};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Depth {
+  pub asks: Vec<[f32; 2]>,
+  pub bids: Vec<[f32; 2]>,
+}
+
+impl Depth {
+  pub fn into_order_book(self, ticker_name: String) -> OrderBook {
+    let into_levels = |levels: Vec<[f32; 2]>| {
+      levels
+        .into_iter()
+        .map(|[price, quantity]| Level { price, quantity })
+        .collect()
+    };
+
+    OrderBook {
+      asks: into_levels(self.asks),
+      bids: into_levels(self.bids),
+      timestamp: Utc::now().timestamp_millis(),
+      ..OrderBook::new(
+        Exchange::BitkubEx.get_name(),
+        Exchange::BitkubEx.get_key(ticker_name.clone()),
+        ticker_name,
+      )
+    }
+  }
+}
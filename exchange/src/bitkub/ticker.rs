@@ -1,6 +1,13 @@
+use crate::exchange::Exchange;
 use chrono::Utc;
+use hodler::models::ticker::MarketTicker;
 use serde::Deserialize;
 
+#[derive(Deserialize)]
+pub struct BitkubFrame {
+  pub stream: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Ticker {
   #[serde(rename = "lowestAsk")]
@@ -13,10 +20,22 @@ pub struct Ticker {
   pub volume: f32,
   #[serde(rename = "percentChange")]
   pub change: f32,
-  #[serde(default = "get_current_timestamp")]
-  pub timestamp: i64,
 }
 
-fn get_current_timestamp() -> i64 {
-  Utc::now().timestamp_millis()
+impl From<Ticker> for MarketTicker {
+  fn from(ticker: Ticker) -> Self {
+    Self {
+      exchange: Exchange::BitkubEx.get_name(),
+      symbol: Exchange::BitkubEx.get_key(ticker.ticker_name.clone()),
+      ticker_name: ticker.ticker_name,
+      ask_price: ticker.ask_price,
+      ask_quantity: None,
+      bid_price: ticker.bid_price,
+      bid_quantity: None,
+      volume: Some(ticker.volume),
+      percent_change: Some(ticker.change),
+      exchange_timestamp: None,
+      received_at: Utc::now().timestamp_millis(),
+    }
+  }
 }
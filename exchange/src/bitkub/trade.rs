@@ -0,0 +1,28 @@
+use crate::exchange::Exchange;
+use hodler::models::trade::MarketTrade;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Trade {
+  #[serde(rename = "stream")]
+  pub ticker_name: String,
+  #[serde(rename = "rat")]
+  pub price: f32,
+  #[serde(rename = "amt")]
+  pub quantity: f32,
+  #[serde(rename = "ts")]
+  pub timestamp: i64,
+}
+
+impl From<Trade> for MarketTrade {
+  fn from(trade: Trade) -> Self {
+    Self {
+      exchange: Exchange::BitkubEx.get_name(),
+      symbol: Exchange::BitkubEx.get_key(trade.ticker_name.clone()),
+      ticker_name: trade.ticker_name,
+      price: trade.price,
+      quantity: trade.quantity,
+      timestamp: trade.timestamp * 1000,
+    }
+  }
+}
@@ -0,0 +1,142 @@
+pub mod ticker;
+
+use crate::client::ExchangeClient;
+use crate::error::ParseError;
+use crate::event::MarketEvent;
+use crate::exchange::Exchange;
+use config::Config;
+use hodler::models::ticker::MarketTicker;
+use serde_json::{from_str, json};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use ticker::{BookTicker, BookUpdate, BybitBook, BybitFrame, BybitTicker, Ticker};
+use tokio_tungstenite::tungstenite::Message;
+
+// Bybit spot accepts at most 10 args per subscribe request.
+const SUBSCRIPTION_BATCH_SIZE: usize = 10;
+
+#[derive(Clone)]
+pub struct Client {
+  pub name: Exchange,
+  ticker_url: String,
+  topics: Vec<String>,
+  tickers: Arc<Mutex<HashMap<String, Ticker>>>,
+  book_tickers: Arc<Mutex<HashMap<String, BookTicker>>>,
+}
+
+impl Client {
+  pub fn new() -> Self {
+    Self::with_config(&Config::default())
+  }
+
+  pub fn with_config(config: &Config) -> Self {
+    let name = Exchange::BybitEx;
+    let tickers = name.get_tickers(&name.get_symbols(config));
+    let topics = tickers
+      .iter()
+      .flat_map(|ticker| [ticker.clone(), ticker.replace("tickers.", "orderbook.1.")])
+      .collect();
+
+    Self {
+      ticker_url: name.get_ticker_url_for(&tickers),
+      topics,
+      tickers: Arc::new(Mutex::new(HashMap::new())),
+      book_tickers: Arc::new(Mutex::new(HashMap::new())),
+      name,
+    }
+  }
+
+  fn merge_ticker(&self, ticker: Ticker) -> Option<MarketTicker> {
+    let market_ticker = self
+      .book_tickers
+      .lock()
+      .unwrap()
+      .get(&ticker.ticker_name)
+      .and_then(|book_ticker| book_ticker.get_market_ticker(&ticker));
+
+    self
+      .tickers
+      .lock()
+      .unwrap()
+      .insert(ticker.ticker_name.clone(), ticker);
+
+    market_ticker
+  }
+
+  fn merge_book_ticker(&self, update: BookUpdate) -> Option<MarketTicker> {
+    let ticker_name = update.ticker_name.clone();
+    let mut book_tickers = self.book_tickers.lock().unwrap();
+    let book_ticker = book_tickers.entry(ticker_name.clone()).or_default();
+    book_ticker.apply(update);
+
+    self
+      .tickers
+      .lock()
+      .unwrap()
+      .get(&ticker_name)
+      .and_then(|ticker| book_ticker.get_market_ticker(ticker))
+  }
+}
+
+impl Default for Client {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ExchangeClient for Client {
+  fn name(&self) -> Exchange {
+    self.name.clone()
+  }
+
+  fn ticker_url(&self) -> String {
+    self.ticker_url.clone()
+  }
+
+  fn subscriptions(&self) -> Vec<String> {
+    self
+      .topics
+      .chunks(SUBSCRIPTION_BATCH_SIZE)
+      .map(|tickers| json!({ "op": "subscribe", "args": tickers }).to_string())
+      .collect()
+  }
+
+  fn ping_interval(&self) -> Option<Duration> {
+    Some(Duration::from_secs(20))
+  }
+
+  fn ping(&self) -> Message {
+    Message::Text(json!({ "op": "ping" }).to_string().into())
+  }
+
+  fn decode(&self, text: &str) -> Vec<Result<MarketEvent, ParseError>> {
+    let frame = match from_str::<BybitFrame>(text) {
+      Ok(frame) => frame,
+      Err(err) => return vec![Err(err.into())],
+    };
+
+    match (frame.op.as_deref(), frame.topic) {
+      (Some("ping" | "pong"), _) => Vec::new(),
+      (Some(_), _) if frame.success == Some(false) => {
+        vec![Err(ParseError::SubscriptionRejected(text.to_string()))]
+      }
+      (Some(_), _) => vec![Err(ParseError::SubscriptionAck(text.to_string()))],
+      (None, Some(topic)) if topic.starts_with("tickers.") => from_str::<BybitTicker>(text)
+        .map_err(ParseError::from)
+        .and_then(Ticker::try_from)
+        .map(|ticker| self.merge_ticker(ticker).map(MarketEvent::from))
+        .transpose()
+        .into_iter()
+        .collect(),
+      (None, Some(topic)) if topic.starts_with("orderbook.") => from_str::<BybitBook>(text)
+        .map_err(ParseError::from)
+        .and_then(BookUpdate::try_from)
+        .map(|update| self.merge_book_ticker(update).map(MarketEvent::from))
+        .transpose()
+        .into_iter()
+        .collect(),
+      (None, topic) => vec![Err(ParseError::UnknownStream(topic.unwrap_or_default()))],
+    }
+  }
+}
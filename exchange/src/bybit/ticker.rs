@@ -0,0 +1,135 @@
+use crate::error::{parse_number, ParseError};
+use crate::exchange::Exchange;
+use chrono::Utc;
+use hodler::models::ticker::MarketTicker;
+use serde::Deserialize;
+
+#[derive(Clone, Debug)]
+pub struct Ticker {
+  pub ticker_name: String,
+  pub change: f32,
+  pub volume: f32,
+  pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct BookUpdate {
+  pub ticker_name: String,
+  pub ask: Option<(f32, f32)>,
+  pub bid: Option<(f32, f32)>,
+  pub snapshot: bool,
+  pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BookTicker {
+  pub ask: Option<(f32, f32)>,
+  pub bid: Option<(f32, f32)>,
+  pub timestamp: i64,
+}
+
+#[derive(Deserialize)]
+pub struct BybitFrame {
+  pub op: Option<String>,
+  pub success: Option<bool>,
+  pub topic: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BybitTicker {
+  ts: i64,
+  data: Data,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct Data {
+  symbol: String,
+  price24hPcnt: String,
+  volume24h: String,
+}
+
+#[derive(Deserialize)]
+pub struct BybitBook {
+  ts: i64,
+  #[serde(rename = "type")]
+  kind: String,
+  data: BookData,
+}
+
+#[derive(Deserialize)]
+struct BookData {
+  s: String,
+  a: Vec<[String; 2]>,
+  b: Vec<[String; 2]>,
+}
+
+impl TryFrom<BybitTicker> for Ticker {
+  type Error = ParseError;
+
+  fn try_from(ticker: BybitTicker) -> Result<Self, Self::Error> {
+    Ok(Ticker {
+      ticker_name: ticker.data.symbol.to_lowercase(),
+      change: parse_number("price24hPcnt", &ticker.data.price24hPcnt)? * 100.0,
+      volume: parse_number("volume24h", &ticker.data.volume24h)?,
+      timestamp: ticker.ts,
+    })
+  }
+}
+
+impl TryFrom<BybitBook> for BookUpdate {
+  type Error = ParseError;
+
+  fn try_from(book: BybitBook) -> Result<Self, Self::Error> {
+    Ok(BookUpdate {
+      ask: get_level("a", &book.data.a)?,
+      bid: get_level("b", &book.data.b)?,
+      ticker_name: book.data.s.to_lowercase(),
+      snapshot: book.kind == "snapshot",
+      timestamp: book.ts,
+    })
+  }
+}
+
+impl BookTicker {
+  pub fn apply(&mut self, update: BookUpdate) {
+    let merge = |side: Option<(f32, f32)>, level: Option<(f32, f32)>| match level {
+      Some((_, 0.0)) => None,
+      None if !update.snapshot => side,
+      level => level,
+    };
+
+    self.ask = merge(self.ask, update.ask);
+    self.bid = merge(self.bid, update.bid);
+    self.timestamp = update.timestamp;
+  }
+
+  pub fn get_market_ticker(&self, ticker: &Ticker) -> Option<MarketTicker> {
+    let (ask_price, ask_quantity) = self.ask?;
+    let (bid_price, bid_quantity) = self.bid?;
+
+    Some(MarketTicker {
+      exchange: Exchange::BybitEx.get_name(),
+      symbol: Exchange::BybitEx.get_key(ticker.ticker_name.clone()),
+      ticker_name: ticker.ticker_name.clone(),
+      ask_price,
+      ask_quantity: Some(ask_quantity),
+      bid_price,
+      bid_quantity: Some(bid_quantity),
+      volume: Some(ticker.volume),
+      percent_change: Some(ticker.change),
+      exchange_timestamp: Some(self.timestamp.max(ticker.timestamp)),
+      received_at: Utc::now().timestamp_millis(),
+    })
+  }
+}
+
+fn get_level(
+  field: &'static str,
+  levels: &[[String; 2]],
+) -> Result<Option<(f32, f32)>, ParseError> {
+  levels
+    .first()
+    .map(|[price, quantity]| Ok((parse_number(field, price)?, parse_number(field, quantity)?)))
+    .transpose()
+}
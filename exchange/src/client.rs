@@ -0,0 +1,71 @@
+use crate::error::ParseError;
+use crate::event::MarketEvent;
+use crate::exchange::Exchange;
+use async_trait::async_trait;
+use futures_util::SinkExt;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+pub type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shard {
+  pub ticker_url: String,
+  pub subscriptions: Vec<String>,
+}
+
+#[async_trait]
+pub trait ExchangeClient: Send + Sync {
+  fn name(&self) -> Exchange;
+
+  fn ticker_url(&self) -> String {
+    self.name().get_ticker_url()
+  }
+
+  fn subscriptions(&self) -> Vec<String> {
+    Vec::new()
+  }
+
+  fn get_shards(&self) -> Vec<Shard> {
+    vec![Shard {
+      ticker_url: self.ticker_url(),
+      subscriptions: self.subscriptions(),
+    }]
+  }
+
+  fn get_key(&self, ticker_name: String) -> String {
+    self.name().get_key(ticker_name)
+  }
+
+  fn decode(&self, text: &str) -> Vec<Result<MarketEvent, ParseError>>;
+
+  fn ping_interval(&self) -> Option<Duration> {
+    None
+  }
+
+  fn ping(&self) -> Message {
+    Message::Ping(Default::default())
+  }
+
+  fn poll_interval(&self) -> Option<Duration> {
+    None
+  }
+
+  async fn poll(&self) -> Vec<Result<MarketEvent, ParseError>> {
+    Vec::new()
+  }
+
+  async fn connect_ws(&self, shard: &Shard) -> Result<WsStream, WsError> {
+    let (mut stream, _) = connect_async(&shard.ticker_url).await?;
+
+    for subscription in shard.subscriptions.iter() {
+      stream
+        .send(Message::Text(subscription.clone().into()))
+        .await?;
+    }
+
+    Ok(stream)
+  }
+}
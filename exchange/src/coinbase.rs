@@ -0,0 +1,88 @@
+pub mod ticker;
+
+use crate::client::ExchangeClient;
+use crate::error::ParseError;
+use crate::event::MarketEvent;
+use crate::exchange::Exchange;
+use config::Config;
+use hodler::models::ticker::MarketTicker;
+use serde_json::{from_str, json};
+use ticker::{CoinbaseFrame, CoinbaseTicker};
+
+#[derive(Clone)]
+pub struct Client {
+  pub name: Exchange,
+  ticker_url: String,
+  tickers: Vec<String>,
+}
+
+impl Client {
+  pub fn new() -> Self {
+    Self::with_config(&Config::default())
+  }
+
+  pub fn with_config(config: &Config) -> Self {
+    let name = Exchange::CoinbaseEx;
+    let tickers = name.get_tickers(&name.get_symbols(config));
+
+    Self {
+      ticker_url: name.get_ticker_url_for(&tickers),
+      tickers,
+      name,
+    }
+  }
+}
+
+impl Default for Client {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ExchangeClient for Client {
+  fn name(&self) -> Exchange {
+    self.name.clone()
+  }
+
+  fn ticker_url(&self) -> String {
+    self.ticker_url.clone()
+  }
+
+  fn subscriptions(&self) -> Vec<String> {
+    ["ticker", "heartbeats"]
+      .into_iter()
+      .map(|channel| {
+        json!({
+          "type": "subscribe",
+          "product_ids": self.tickers.clone(),
+          "channel": channel,
+        })
+        .to_string()
+      })
+      .collect()
+  }
+
+  fn decode(&self, text: &str) -> Vec<Result<MarketEvent, ParseError>> {
+    let frame = match from_str::<CoinbaseFrame>(text) {
+      Ok(frame) => frame,
+      Err(err) => return vec![Err(err.into())],
+    };
+
+    match (frame.kind.as_deref(), frame.channel.as_deref()) {
+      (Some("error"), _) => vec![Err(ParseError::SubscriptionRejected(text.to_string()))],
+      (_, Some("subscriptions")) => vec![Err(ParseError::SubscriptionAck(text.to_string()))],
+      (_, Some("heartbeats")) => Vec::new(),
+      (_, Some("ticker")) => match from_str::<CoinbaseTicker>(text) {
+        Ok(ticker) => ticker
+          .into_tickers()
+          .into_iter()
+          .map(|t| t.map(MarketTicker::from).map(MarketEvent::from))
+          .collect(),
+        Err(err) => vec![Err(err.into())],
+      },
+      (_, channel) => vec![Err(ParseError::UnknownStream(
+        channel.unwrap_or_default().to_string(),
+      ))],
+    }
+  }
+}
@@ -0,0 +1,88 @@
+use crate::error::{parse_number, ParseError};
+use crate::exchange::Exchange;
+use chrono::{DateTime, Utc};
+use hodler::models::ticker::MarketTicker;
+use serde::Deserialize;
+
+#[derive(Debug)]
+pub struct Ticker {
+  pub ask_price: f32,
+  pub ask_quantity: f32,
+  pub bid_price: f32,
+  pub bid_quantity: f32,
+  pub ticker_name: String,
+  pub change: f32,
+  pub volume: f32,
+  pub timestamp: i64,
+}
+
+#[derive(Deserialize)]
+pub struct CoinbaseFrame {
+  pub channel: Option<String>,
+  #[serde(rename = "type")]
+  pub kind: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CoinbaseTicker {
+  timestamp: DateTime<Utc>,
+  events: Vec<Event>,
+}
+
+#[derive(Deserialize)]
+struct Event {
+  tickers: Vec<Data>,
+}
+
+#[derive(Deserialize)]
+struct Data {
+  product_id: String,
+  best_ask: String,
+  best_ask_quantity: String,
+  best_bid: String,
+  best_bid_quantity: String,
+  price_percent_chg_24_h: String,
+  volume_24_h: String,
+}
+
+impl CoinbaseTicker {
+  pub fn into_tickers(self) -> Vec<Result<Ticker, ParseError>> {
+    let timestamp = self.timestamp.timestamp_millis();
+
+    self
+      .events
+      .into_iter()
+      .flat_map(|event| event.tickers)
+      .map(|ticker| {
+        Ok(Ticker {
+          ask_price: parse_number("best_ask", &ticker.best_ask)?,
+          ask_quantity: parse_number("best_ask_quantity", &ticker.best_ask_quantity)?,
+          bid_price: parse_number("best_bid", &ticker.best_bid)?,
+          bid_quantity: parse_number("best_bid_quantity", &ticker.best_bid_quantity)?,
+          ticker_name: ticker.product_id.to_lowercase(),
+          change: parse_number("price_percent_chg_24_h", &ticker.price_percent_chg_24_h)?,
+          volume: parse_number("volume_24_h", &ticker.volume_24_h)?,
+          timestamp,
+        })
+      })
+      .collect()
+  }
+}
+
+impl From<Ticker> for MarketTicker {
+  fn from(ticker: Ticker) -> Self {
+    Self {
+      exchange: Exchange::CoinbaseEx.get_name(),
+      symbol: Exchange::CoinbaseEx.get_key(ticker.ticker_name.clone()),
+      ticker_name: ticker.ticker_name,
+      ask_price: ticker.ask_price,
+      ask_quantity: Some(ticker.ask_quantity),
+      bid_price: ticker.bid_price,
+      bid_quantity: Some(ticker.bid_quantity),
+      volume: Some(ticker.volume),
+      percent_change: Some(ticker.change),
+      exchange_timestamp: Some(ticker.timestamp),
+      received_at: Utc::now().timestamp_millis(),
+    }
+  }
+}
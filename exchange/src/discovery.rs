@@ -0,0 +1,243 @@
+use crate::error::DiscoveryError;
+use crate::exchange::Exchange;
+use crate::rest::get_text;
+use config::aliases::Aliases;
+use futures_util::future::join_all;
+use log::warn;
+use serde::Deserialize;
+use serde_json::from_str;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[derive(Deserialize)]
+struct BinanceExchangeInfo {
+  symbols: Vec<BinanceSymbol>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceSymbol {
+  status: String,
+  base_asset: String,
+  quote_asset: String,
+}
+
+#[derive(Deserialize)]
+struct BitkubSymbols {
+  result: Vec<BitkubSymbol>,
+}
+
+#[derive(Deserialize)]
+struct BitkubSymbol {
+  symbol: String,
+}
+
+#[derive(Deserialize)]
+struct KrakenAssetPairs {
+  result: HashMap<String, KrakenAssetPair>,
+}
+
+#[derive(Deserialize)]
+struct KrakenAssetPair {
+  wsname: Option<String>,
+  status: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CoinbaseProducts {
+  products: Vec<CoinbaseProduct>,
+}
+
+#[derive(Deserialize)]
+struct CoinbaseProduct {
+  base_currency_id: String,
+  quote_currency_id: String,
+  status: String,
+  trading_disabled: bool,
+}
+
+#[derive(Deserialize)]
+struct OkxInstruments {
+  data: Vec<OkxInstrument>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxInstrument {
+  base_ccy: String,
+  quote_ccy: String,
+  state: String,
+}
+
+#[derive(Deserialize)]
+struct BybitInstruments {
+  result: BybitInstrumentList,
+}
+
+#[derive(Deserialize)]
+struct BybitInstrumentList {
+  list: Vec<BybitInstrument>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitInstrument {
+  base_coin: String,
+  quote_coin: String,
+  status: String,
+}
+
+pub struct Discovery {
+  http: reqwest::Client,
+  urls: HashMap<String, String>,
+}
+
+impl Discovery {
+  pub fn new() -> Self {
+    Self::with_urls(HashMap::new())
+  }
+
+  pub fn with_urls(urls: HashMap<String, String>) -> Self {
+    Self {
+      http: reqwest::Client::new(),
+      urls,
+    }
+  }
+
+  pub async fn get_symbols(&self) -> Result<Vec<String>, DiscoveryError> {
+    let binance = self.get_bases(&Exchange::BinanceEx).await?;
+    let bitkub = self.get_bases(&Exchange::BitkubEx).await?;
+    let symbols = binance
+      .intersection(&bitkub)
+      .cloned()
+      .collect::<Vec<String>>();
+
+    match symbols.is_empty() {
+      true => Err(DiscoveryError::NoSymbols),
+      false => Ok(symbols),
+    }
+  }
+
+  pub async fn get_listings(&self, exchanges: &[Exchange]) -> BTreeMap<String, Vec<String>> {
+    let listings = join_all(exchanges.iter().map(|exchange| self.get_listing(exchange))).await;
+
+    exchanges
+      .iter()
+      .zip(listings)
+      .filter_map(|(exchange, listing)| match listing {
+        Ok(listing) => Some((exchange.get_name(), listing.into_iter().collect())),
+        Err(err) => {
+          warn!(target: &exchange.get_name(), "listing unavailable, not filtering symbols: {err}");
+          None
+        }
+      })
+      .collect()
+  }
+
+  pub async fn get_listing(&self, exchange: &Exchange) -> Result<BTreeSet<String>, DiscoveryError> {
+    let url = self
+      .urls
+      .get(&exchange.get_name())
+      .cloned()
+      .unwrap_or_else(|| exchange.get_rest_url());
+    let pairs = match exchange {
+      Exchange::BinanceEx => {
+        from_str::<BinanceExchangeInfo>(&self.get(format!("{url}/api/v3/exchangeInfo")).await?)?
+          .symbols
+          .into_iter()
+          .filter(|s| s.status == "TRADING")
+          .map(|s| (s.base_asset, s.quote_asset))
+          .collect::<Vec<_>>()
+      }
+      Exchange::BitkubEx => {
+        from_str::<BitkubSymbols>(&self.get(format!("{url}/api/market/symbols")).await?)?
+          .result
+          .into_iter()
+          .filter_map(|s| {
+            let (quote, base) = s.symbol.split_once('_')?;
+            Some((base.to_string(), quote.to_string()))
+          })
+          .collect()
+      }
+      #[cfg(feature = "ftx")]
+      Exchange::FtxEx => return Err(DiscoveryError::Unsupported(exchange.get_name())),
+      Exchange::KrakenEx => {
+        from_str::<KrakenAssetPairs>(&self.get(format!("{url}/0/public/AssetPairs")).await?)?
+          .result
+          .into_values()
+          .filter(|p| p.status.as_deref().unwrap_or("online") == "online")
+          .filter_map(|p| {
+            let wsname = p.wsname?;
+            let (base, quote) = wsname.split_once('/')?;
+            Some((base.to_string(), quote.to_string()))
+          })
+          .collect()
+      }
+      Exchange::CoinbaseEx => from_str::<CoinbaseProducts>(
+        &self
+          .get(format!(
+            "{url}/api/v3/brokerage/market/products?product_type=SPOT"
+          ))
+          .await?,
+      )?
+      .products
+      .into_iter()
+      .filter(|p| p.status == "online" && !p.trading_disabled)
+      .map(|p| (p.base_currency_id, p.quote_currency_id))
+      .collect(),
+      Exchange::OkxEx => from_str::<OkxInstruments>(
+        &self
+          .get(format!("{url}/api/v5/public/instruments?instType=SPOT"))
+          .await?,
+      )?
+      .data
+      .into_iter()
+      .filter(|i| i.state == "live")
+      .map(|i| (i.base_ccy, i.quote_ccy))
+      .collect(),
+      Exchange::BybitEx => from_str::<BybitInstruments>(
+        &self
+          .get(format!("{url}/v5/market/instruments-info?category=spot"))
+          .await?,
+      )?
+      .result
+      .list
+      .into_iter()
+      .filter(|i| i.status == "Trading")
+      .map(|i| (i.base_coin, i.quote_coin))
+      .collect(),
+    };
+    let venue = exchange.get_name();
+    let get_key = |asset: String| Aliases::get().get_key(&venue, &asset.to_lowercase());
+
+    Ok(
+      pairs
+        .into_iter()
+        .map(|(base, quote)| format!("{}/{}", get_key(base), get_key(quote)))
+        .collect(),
+    )
+  }
+
+  async fn get_bases(&self, exchange: &Exchange) -> Result<BTreeSet<String>, DiscoveryError> {
+    let quote = format!("/{}", exchange.get_quote().get_name());
+
+    Ok(
+      self
+        .get_listing(exchange)
+        .await?
+        .iter()
+        .filter_map(|pair| pair.strip_suffix(&quote))
+        .map(String::from)
+        .collect(),
+    )
+  }
+
+  async fn get(&self, url: String) -> Result<String, DiscoveryError> {
+    Ok(get_text(&self.http, &url).await?)
+  }
+}
+
+impl Default for Discovery {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,144 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidNumber { field: &'static str, raw: String },
+  UnknownStream(String),
+  SubscriptionAck(String),
+  SubscriptionRejected(String),
+  InvalidFrame(serde_json::Error),
+}
+
+impl ParseError {
+  pub fn get_kind(&self) -> &'static str {
+    match self {
+      Self::InvalidNumber { .. } => "invalid_number",
+      Self::UnknownStream(_) => "unknown_stream",
+      Self::SubscriptionAck(_) => "subscription_ack",
+      Self::SubscriptionRejected(_) => "subscription_rejected",
+      Self::InvalidFrame(_) => "invalid_frame",
+    }
+  }
+}
+
+impl Display for ParseError {
+  fn fmt(&self, f: &mut Formatter) -> Result {
+    match self {
+      Self::InvalidNumber { field, raw } => write!(f, "invalid number in `{field}`: {raw:?}"),
+      Self::UnknownStream(stream) => write!(f, "unknown stream: {stream}"),
+      Self::SubscriptionAck(ack) => write!(f, "subscription acknowledgement: {ack}"),
+      Self::SubscriptionRejected(ack) => write!(f, "subscription rejected: {ack}"),
+      Self::InvalidFrame(err) => write!(f, "invalid frame: {err}"),
+    }
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<serde_json::Error> for ParseError {
+  fn from(err: serde_json::Error) -> Self {
+    Self::InvalidFrame(err)
+  }
+}
+
+#[derive(Debug)]
+pub enum DiscoveryError {
+  Request(reqwest::Error),
+  InvalidResponse(serde_json::Error),
+  NoSymbols,
+  Unsupported(String),
+}
+
+impl Display for DiscoveryError {
+  fn fmt(&self, f: &mut Formatter) -> Result {
+    match self {
+      Self::Request(err) => write!(f, "request failed: {err}"),
+      Self::InvalidResponse(err) => write!(f, "invalid response: {err}"),
+      Self::NoSymbols => write!(f, "no symbols are tradable on every venue"),
+      Self::Unsupported(venue) => write!(f, "listing is not supported for {venue}"),
+    }
+  }
+}
+
+impl std::error::Error for DiscoveryError {}
+
+impl From<reqwest::Error> for DiscoveryError {
+  fn from(err: reqwest::Error) -> Self {
+    Self::Request(err)
+  }
+}
+
+impl From<serde_json::Error> for DiscoveryError {
+  fn from(err: serde_json::Error) -> Self {
+    Self::InvalidResponse(err)
+  }
+}
+
+#[derive(Debug)]
+pub enum NetworkError {
+  Request(reqwest::Error),
+  InvalidResponse(serde_json::Error),
+  Rejected(String),
+}
+
+impl Display for NetworkError {
+  fn fmt(&self, f: &mut Formatter) -> Result {
+    match self {
+      Self::Request(err) => write!(f, "request failed: {err}"),
+      Self::InvalidResponse(err) => write!(f, "invalid response: {err}"),
+      Self::Rejected(message) => write!(f, "request rejected: {message}"),
+    }
+  }
+}
+
+impl std::error::Error for NetworkError {}
+
+impl From<reqwest::Error> for NetworkError {
+  fn from(err: reqwest::Error) -> Self {
+    Self::Request(err)
+  }
+}
+
+impl From<serde_json::Error> for NetworkError {
+  fn from(err: serde_json::Error) -> Self {
+    Self::InvalidResponse(err)
+  }
+}
+
+#[derive(Debug)]
+pub enum FxError {
+  Request(reqwest::Error),
+  InvalidResponse(serde_json::Error),
+  MissingRate,
+}
+
+impl Display for FxError {
+  fn fmt(&self, f: &mut Formatter) -> Result {
+    match self {
+      Self::Request(err) => write!(f, "request failed: {err}"),
+      Self::InvalidResponse(err) => write!(f, "invalid response: {err}"),
+      Self::MissingRate => write!(f, "no positive THB rate in response"),
+    }
+  }
+}
+
+impl std::error::Error for FxError {}
+
+impl From<reqwest::Error> for FxError {
+  fn from(err: reqwest::Error) -> Self {
+    Self::Request(err)
+  }
+}
+
+impl From<serde_json::Error> for FxError {
+  fn from(err: serde_json::Error) -> Self {
+    Self::InvalidResponse(err)
+  }
+}
+
+pub fn parse_number(field: &'static str, raw: &str) -> std::result::Result<f32, ParseError> {
+  raw.parse::<f32>().map_err(|_| ParseError::InvalidNumber {
+    field,
+    raw: raw.to_string(),
+  })
+}
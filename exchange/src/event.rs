@@ -0,0 +1,36 @@
+use hodler::models::book::OrderBook;
+use hodler::models::ticker::MarketTicker;
+use hodler::models::trade::MarketTrade;
+use hodler::models::triangle::MarketPair;
+
+#[derive(Clone, Debug)]
+pub enum MarketEvent {
+  Ticker(MarketTicker),
+  OrderBook(OrderBook),
+  Trade(MarketTrade),
+  Pair(MarketPair),
+}
+
+impl From<MarketTicker> for MarketEvent {
+  fn from(market_ticker: MarketTicker) -> Self {
+    Self::Ticker(market_ticker)
+  }
+}
+
+impl From<OrderBook> for MarketEvent {
+  fn from(order_book: OrderBook) -> Self {
+    Self::OrderBook(order_book)
+  }
+}
+
+impl From<MarketTrade> for MarketEvent {
+  fn from(market_trade: MarketTrade) -> Self {
+    Self::Trade(market_trade)
+  }
+}
+
+impl From<MarketPair> for MarketEvent {
+  fn from(market_pair: MarketPair) -> Self {
+    Self::Pair(market_pair)
+  }
+}
@@ -1,88 +1,199 @@
+use crate::client::ExchangeClient;
+use config::aliases::Aliases;
+use config::Config;
+use hodler::models::quote::Quote;
+use log::warn;
+
 #[derive(Clone)]
 pub enum Exchange {
   BinanceEx,
   BitkubEx,
+  #[cfg(feature = "ftx")]
   FtxEx,
+  KrakenEx,
+  CoinbaseEx,
+  OkxEx,
+  BybitEx,
 }
 
 impl Exchange {
+  pub fn get_all() -> Vec<Self> {
+    vec![
+      Self::BinanceEx,
+      Self::BitkubEx,
+      #[cfg(feature = "ftx")]
+      Self::FtxEx,
+      Self::KrakenEx,
+      Self::CoinbaseEx,
+      Self::OkxEx,
+      Self::BybitEx,
+    ]
+  }
+
+  pub fn get_enabled(config: &Config) -> Vec<Self> {
+    config
+      .exchanges
+      .iter()
+      .filter_map(|name| match Self::from_name(name) {
+        Some(exchange) => Some(exchange),
+        None => {
+          warn!("{name} is not available in this build, skipping");
+          None
+        }
+      })
+      .collect()
+  }
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::get_all().into_iter().find(|e| e.get_name() == name)
+  }
+
+  pub fn get_client(&self, config: &Config) -> Box<dyn ExchangeClient> {
+    match self {
+      Self::BinanceEx => Box::new(crate::binance::Client::with_config(config)),
+      Self::BitkubEx => Box::new(crate::bitkub::Client::with_config(config)),
+      #[cfg(feature = "ftx")]
+      Self::FtxEx => Box::new(crate::ftx::Client::with_config(config)),
+      Self::KrakenEx => Box::new(crate::kraken::Client::with_config(config)),
+      Self::CoinbaseEx => Box::new(crate::coinbase::Client::with_config(config)),
+      Self::OkxEx => Box::new(crate::okx::Client::with_config(config)),
+      Self::BybitEx => Box::new(crate::bybit::Client::with_config(config)),
+    }
+  }
+
   pub fn get_name(&self) -> String {
     match self {
       Self::BinanceEx => "binance",
       Self::BitkubEx => "bitkub",
+      #[cfg(feature = "ftx")]
       Self::FtxEx => "ftx",
+      Self::KrakenEx => "kraken",
+      Self::CoinbaseEx => "coinbase",
+      Self::OkxEx => "okx",
+      Self::BybitEx => "bybit",
     }
     .to_string()
   }
 
   pub fn get_ticker_url(&self) -> String {
+    self.get_ticker_url_for(&self.get_tickers(&Config::default().symbols))
+  }
+
+  pub fn get_ticker_url_for(&self, tickers: &[String]) -> String {
     let ticker_url_format = match self {
       Self::BinanceEx => "wss://stream.binance.com:9443/stream?streams={}",
       Self::BitkubEx => "wss://api.bitkub.com/websocket-api/{}",
+      #[cfg(feature = "ftx")]
       Self::FtxEx => "wss://ftx.com/ws/",
+      Self::KrakenEx => "wss://ws.kraken.com/v2",
+      Self::CoinbaseEx => "wss://advanced-trade-ws.coinbase.com",
+      Self::OkxEx => "wss://ws.okx.com:8443/ws/v5/public",
+      Self::BybitEx => "wss://stream.bybit.com/v5/public/spot",
     };
 
-    let tickers = self.get_tickers().join(self.get_ticker_sep());
+    let tickers = tickers.join(self.get_ticker_sep());
 
     ticker_url_format.replace("{}", &tickers)
   }
 
-  pub fn get_tickers(&self) -> Vec<String> {
+  pub fn get_rest_url(&self) -> String {
+    match self {
+      Self::BinanceEx => "https://api.binance.com",
+      Self::BitkubEx => "https://api.bitkub.com",
+      #[cfg(feature = "ftx")]
+      Self::FtxEx => "https://ftx.com/api",
+      Self::KrakenEx => "https://api.kraken.com",
+      Self::CoinbaseEx => "https://api.coinbase.com",
+      Self::OkxEx => "https://www.okx.com",
+      Self::BybitEx => "https://api.bybit.com",
+    }
+    .to_string()
+  }
+
+  pub fn get_quote(&self) -> Quote {
+    match self {
+      Self::BinanceEx => Quote::Usdt,
+      Self::BitkubEx => Quote::Thb,
+      #[cfg(feature = "ftx")]
+      Self::FtxEx => Quote::Usd,
+      Self::KrakenEx => Quote::Usd,
+      Self::CoinbaseEx => Quote::Usd,
+      Self::OkxEx => Quote::Usdt,
+      Self::BybitEx => Quote::Usdt,
+    }
+  }
+
+  pub fn get_symbols(&self, config: &Config) -> Vec<String> {
+    let quote = self.get_quote().get_name();
+    let currency = config::CURRENCY_SYMBOL.to_string();
+    let symbols = match config.symbols.contains(&currency) {
+      true => config.symbols.clone(),
+      false => [vec![currency], config.symbols.clone()].concat(),
+    };
+
+    match config.listings.get(&self.get_name()) {
+      Some(listing) => symbols
+        .into_iter()
+        .filter(|symbol| listing.contains(&format!("{symbol}/{quote}")))
+        .collect(),
+      None => symbols,
+    }
+  }
+
+  pub fn get_tickers(&self, symbols: &[String]) -> Vec<String> {
     let ticker_format = match self {
       Self::BinanceEx => "{}usdt@ticker",
       Self::BitkubEx => "market.ticker.thb_{}",
+      #[cfg(feature = "ftx")]
       Self::FtxEx => "{\"op\": \"subscribe\", \"channel\": \"ticker\", \"market\": \"{}/USD\"}",
+      Self::KrakenEx => "{}/USD",
+      Self::CoinbaseEx => "{}-USD",
+      Self::OkxEx => "{}-USDT",
+      Self::BybitEx => "tickers.{}USDT",
     };
 
-    config::SYMBOLS
-      .split(",")
-      .map(|symbol| {
-        ticker_format
-          .clone()
-          .replace("{}", &self.get_ticker(symbol))
-      })
+    symbols
+      .iter()
+      .map(|symbol| ticker_format.replace("{}", &self.get_ticker(symbol)))
       .collect::<Vec<String>>()
   }
 
   fn get_ticker(&self, symbol: &str) -> String {
+    let ticker = Aliases::get().get_ticker(&self.get_name(), symbol);
+
     match self {
-      Self::BinanceEx => match symbol.clone() {
-        "luna" => "lunc",
-        "luna2" => "luna",
-        "pow" => "powr",
-        symbol => &symbol,
-      },
-      Self::BitkubEx => match symbol.clone() {
-        "powr" => "pow",
-        symbol => &symbol,
-      },
-      Self::FtxEx => symbol,
+      Self::BinanceEx | Self::BitkubEx => ticker,
+      #[cfg(feature = "ftx")]
+      Self::FtxEx => ticker,
+      Self::KrakenEx | Self::CoinbaseEx | Self::OkxEx | Self::BybitEx => ticker.to_uppercase(),
     }
-    .to_string()
   }
 
   fn get_ticker_sep(&self) -> &str {
     match self {
       Self::BinanceEx => "/",
       Self::BitkubEx => ",",
+      #[cfg(feature = "ftx")]
       Self::FtxEx => "",
+      Self::KrakenEx => "",
+      Self::CoinbaseEx => "",
+      Self::OkxEx => "",
+      Self::BybitEx => "",
     }
   }
 
   pub fn get_key(&self, symbol: String) -> String {
-    match self {
-      Self::BinanceEx => match symbol.replace("usdt", "").as_str() {
-        "luna" => "luna2",
-        "lunc" => "luna",
-        symbol => &symbol,
-      }
-      .to_string(),
-      Self::BitkubEx => match symbol.replace("market.ticker.thb_", "").as_str() {
-        "pow" => "powr",
-        symbol => &symbol,
-      }
-      .to_string(),
+    let ticker = match self {
+      Self::BinanceEx => symbol.replace("usdt", ""),
+      Self::BitkubEx => symbol.rsplit("thb_").next().unwrap_or_default().to_string(),
+      #[cfg(feature = "ftx")]
       Self::FtxEx => symbol.replace("-USD", ""),
-    }
+      Self::KrakenEx => symbol.to_lowercase().replace("/usd", ""),
+      Self::CoinbaseEx => symbol.to_lowercase().replace("-usd", ""),
+      Self::OkxEx => symbol.to_lowercase().replace("-usdt", ""),
+      Self::BybitEx => symbol.to_lowercase().replace("usdt", ""),
+    };
+
+    Aliases::get().get_key(&self.get_name(), &ticker)
   }
 }
@@ -1,37 +1,79 @@
 pub mod ticker;
 
+use crate::client::ExchangeClient;
+use crate::error::ParseError;
+use crate::event::MarketEvent;
 use crate::exchange::Exchange;
-use futures_util::stream::{iter, SplitStream};
-use futures_util::{SinkExt, StreamExt};
-use tokio::net::TcpStream;
-use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use config::Config;
+use hodler::models::ticker::MarketTicker;
+use serde_json::from_str;
+use std::time::Duration;
+use ticker::Ticker;
+use tokio_tungstenite::tungstenite::Message;
 
 #[derive(Clone)]
 pub struct Client {
   pub name: Exchange,
   ticker_url: String,
+  tickers: Vec<String>,
 }
 
 impl Client {
   pub fn new() -> Self {
+    Self::with_config(&Config::default())
+  }
+
+  pub fn with_config(config: &Config) -> Self {
+    let name = Exchange::FtxEx;
+    let tickers = name.get_tickers(&name.get_symbols(config));
+
     Self {
-      name: Exchange::FtxEx.clone(),
-      ticker_url: Exchange::FtxEx.get_ticker_url(),
+      ticker_url: name.get_ticker_url_for(&tickers),
+      tickers,
+      name,
     }
   }
+}
+
+impl Default for Client {
+  fn default() -> Self {
+    Self::new()
+  }
+}
 
-  pub async fn connect_ws(&self) -> SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>> {
-    let (stream, _) = connect_async(self.ticker_url.clone()).await.unwrap();
-    let (mut write, read) = stream.split();
-    let mut tickers = iter(
-      Exchange::FtxEx
-        .get_tickers()
-        .into_iter()
-        .map(|t| Ok(t.into())),
-    );
+impl ExchangeClient for Client {
+  fn name(&self) -> Exchange {
+    self.name.clone()
+  }
+
+  fn ticker_url(&self) -> String {
+    self.ticker_url.clone()
+  }
 
-    write.send_all(&mut tickers).await.unwrap();
+  fn subscriptions(&self) -> Vec<String> {
+    self.tickers.clone()
+  }
+
+  fn ping_interval(&self) -> Option<Duration> {
+    Some(Duration::from_secs(15))
+  }
+
+  fn ping(&self) -> Message {
+    Message::Text("{\"op\": \"ping\"}".into())
+  }
+
+  fn decode(&self, text: &str) -> Vec<Result<MarketEvent, ParseError>> {
+    if text.contains("type\":\"subscribed") {
+      return vec![Err(ParseError::SubscriptionAck(text.to_string()))];
+    }
+
+    if !text.contains("type\":\"update") {
+      return Vec::new();
+    }
 
-    return read;
+    vec![from_str::<Ticker>(text)
+      .map(MarketTicker::from)
+      .map(MarketEvent::from)
+      .map_err(ParseError::from)]
   }
 }
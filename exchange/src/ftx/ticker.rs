@@ -1,3 +1,6 @@
+use crate::exchange::Exchange;
+use chrono::Utc;
+use hodler::models::ticker::MarketTicker;
 use serde::Deserialize;
 use serde::Deserializer;
 
@@ -37,3 +40,21 @@ impl<'de> Deserialize<'de> for Ticker {
     })
   }
 }
+
+impl From<Ticker> for MarketTicker {
+  fn from(ticker: Ticker) -> Self {
+    Self {
+      exchange: Exchange::FtxEx.get_name(),
+      symbol: Exchange::FtxEx.get_key(ticker.symbol.clone()),
+      ticker_name: ticker.symbol,
+      ask_price: ticker.ask_price,
+      ask_quantity: None,
+      bid_price: ticker.bid_price,
+      bid_quantity: None,
+      volume: None,
+      percent_change: None,
+      exchange_timestamp: Some((ticker.time * 1000.0) as i64),
+      received_at: Utc::now().timestamp_millis(),
+    }
+  }
+}
@@ -0,0 +1,44 @@
+use crate::error::FxError;
+use crate::rest::get_text;
+use chrono::Utc;
+use config::Config;
+use hodler::models::premium::FxRate;
+use serde::Deserialize;
+use serde_json::from_str;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct FxRates {
+  rates: HashMap<String, f32>,
+}
+
+pub struct FxSource {
+  http: reqwest::Client,
+  url: String,
+}
+
+impl FxSource {
+  pub fn new(config: &Config) -> Self {
+    Self::with_url(config.fx_url.clone())
+  }
+
+  pub fn with_url(url: String) -> Self {
+    Self {
+      http: reqwest::Client::new(),
+      url,
+    }
+  }
+
+  pub async fn get_rate(&self) -> Result<FxRate, FxError> {
+    let rates = from_str::<FxRates>(&get_text(&self.http, &self.url).await?)?;
+
+    match rates.rates.get("THB") {
+      Some(rate) if *rate > 0.0 => Ok(FxRate {
+        source: self.url.clone(),
+        rate: *rate,
+        timestamp: Utc::now().timestamp_millis(),
+      }),
+      _ => Err(FxError::MissingRate),
+    }
+  }
+}
@@ -0,0 +1,87 @@
+pub mod ticker;
+
+use crate::client::ExchangeClient;
+use crate::error::ParseError;
+use crate::event::MarketEvent;
+use crate::exchange::Exchange;
+use config::Config;
+use hodler::models::ticker::MarketTicker;
+use serde_json::{from_str, json};
+use ticker::{KrakenFrame, KrakenTicker};
+
+#[derive(Clone)]
+pub struct Client {
+  pub name: Exchange,
+  ticker_url: String,
+  tickers: Vec<String>,
+}
+
+impl Client {
+  pub fn new() -> Self {
+    Self::with_config(&Config::default())
+  }
+
+  pub fn with_config(config: &Config) -> Self {
+    let name = Exchange::KrakenEx;
+    let tickers = name.get_tickers(&name.get_symbols(config));
+
+    Self {
+      ticker_url: name.get_ticker_url_for(&tickers),
+      tickers,
+      name,
+    }
+  }
+}
+
+impl Default for Client {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ExchangeClient for Client {
+  fn name(&self) -> Exchange {
+    self.name.clone()
+  }
+
+  fn ticker_url(&self) -> String {
+    self.ticker_url.clone()
+  }
+
+  fn subscriptions(&self) -> Vec<String> {
+    vec![json!({
+      "method": "subscribe",
+      "params": {
+        "channel": "ticker",
+        "symbol": self.tickers.clone(),
+      },
+    })
+    .to_string()]
+  }
+
+  fn decode(&self, text: &str) -> Vec<Result<MarketEvent, ParseError>> {
+    let frame = match from_str::<KrakenFrame>(text) {
+      Ok(frame) => frame,
+      Err(err) => return vec![Err(err.into())],
+    };
+
+    match (frame.method.as_deref(), frame.channel.as_deref()) {
+      (Some(_), _) if frame.success == Some(false) => {
+        vec![Err(ParseError::SubscriptionRejected(text.to_string()))]
+      }
+      (Some(_), _) => vec![Err(ParseError::SubscriptionAck(text.to_string()))],
+      (_, Some("heartbeat" | "status")) => Vec::new(),
+      (_, Some("ticker")) => match from_str::<KrakenTicker>(text) {
+        Ok(ticker) => ticker
+          .data
+          .into_iter()
+          .map(|t| Ok(MarketTicker::from(t).into()))
+          .collect(),
+        Err(err) => vec![Err(err.into())],
+      },
+      (_, channel) => vec![Err(ParseError::UnknownStream(
+        channel.unwrap_or_default().to_string(),
+      ))],
+    }
+  }
+}
@@ -0,0 +1,54 @@
+use crate::exchange::Exchange;
+use chrono::{DateTime, Utc};
+use hodler::models::ticker::MarketTicker;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct KrakenFrame {
+  pub channel: Option<String>,
+  pub method: Option<String>,
+  pub success: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct KrakenTicker {
+  pub data: Vec<Ticker>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Ticker {
+  #[serde(rename = "ask")]
+  pub ask_price: f32,
+  #[serde(rename = "ask_qty")]
+  pub ask_quantity: Option<f32>,
+  #[serde(rename = "bid")]
+  pub bid_price: f32,
+  #[serde(rename = "bid_qty")]
+  pub bid_quantity: Option<f32>,
+  #[serde(rename = "symbol")]
+  pub ticker_name: String,
+  pub volume: f32,
+  #[serde(rename = "change_pct")]
+  pub change: f32,
+  pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl From<Ticker> for MarketTicker {
+  fn from(ticker: Ticker) -> Self {
+    let ticker_name = ticker.ticker_name.to_lowercase();
+
+    Self {
+      exchange: Exchange::KrakenEx.get_name(),
+      symbol: Exchange::KrakenEx.get_key(ticker_name.clone()),
+      ticker_name,
+      ask_price: ticker.ask_price,
+      ask_quantity: ticker.ask_quantity,
+      bid_price: ticker.bid_price,
+      bid_quantity: ticker.bid_quantity,
+      volume: Some(ticker.volume),
+      percent_change: Some(ticker.change),
+      exchange_timestamp: ticker.timestamp.map(|t| t.timestamp_millis()),
+      received_at: Utc::now().timestamp_millis(),
+    }
+  }
+}
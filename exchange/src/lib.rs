@@ -1,4 +1,28 @@
 pub mod binance;
 pub mod bitkub;
+pub mod bybit;
+pub mod client;
+pub mod coinbase;
+pub mod discovery;
+pub mod error;
+pub mod event;
 pub mod exchange;
+#[cfg(feature = "ftx")]
 pub mod ftx;
+pub mod fx;
+pub mod kraken;
+pub mod network;
+pub mod okx;
+pub mod rest;
+pub mod supervisor;
+
+use self::client::ExchangeClient;
+use self::exchange::Exchange;
+use config::Config;
+
+pub fn clients(config: &Config) -> Vec<Box<dyn ExchangeClient>> {
+  Exchange::get_enabled(config)
+    .iter()
+    .map(|exchange| exchange.get_client(config))
+    .collect()
+}
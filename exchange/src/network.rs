@@ -0,0 +1,498 @@
+use crate::error::NetworkError;
+use crate::exchange::Exchange;
+use crate::rest::get_text;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::Utc;
+use config::aliases::Aliases;
+use config::Config;
+use futures_util::future::join_all;
+use hmac::{Hmac, Mac};
+use hodler::models::network::{AssetStatus, Network};
+use reqwest::RequestBuilder;
+use serde::Deserialize;
+use serde_json::from_str;
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::{BTreeMap, HashMap};
+
+const BINANCE_PATH: &str = "/sapi/v1/capital/config/getall";
+const BITKUB_PATH: &str = "/api/v4/crypto/coins";
+const KRAKEN_ASSETS_PATH: &str = "/0/public/Assets";
+const KRAKEN_METHODS_PATH: &str = "/0/private/WithdrawMethods";
+const COINBASE_URL: &str = "https://api.exchange.coinbase.com";
+const COINBASE_PATH: &str = "/currencies";
+const OKX_PATH: &str = "/api/v5/asset/currencies";
+const BYBIT_PATH: &str = "/v5/asset/coin/query-info";
+const BYBIT_RECV_WINDOW: &str = "5000";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceCoin {
+  coin: String,
+  network_list: Vec<BinanceNetwork>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceNetwork {
+  network: String,
+  deposit_enable: bool,
+  withdraw_enable: bool,
+}
+
+#[derive(Deserialize)]
+struct BitkubResponse {
+  code: String,
+  message: String,
+  data: Option<BitkubCoins>,
+}
+
+#[derive(Deserialize)]
+struct BitkubCoins {
+  items: Vec<BitkubCoin>,
+}
+
+#[derive(Deserialize)]
+struct BitkubCoin {
+  symbol: String,
+  networks: Vec<BitkubNetwork>,
+  deposit_enable: bool,
+  withdraw_enable: bool,
+}
+
+#[derive(Deserialize)]
+struct BitkubNetwork {
+  network: String,
+  deposit_enable: bool,
+  withdraw_enable: bool,
+}
+
+#[derive(Deserialize)]
+struct KrakenResponse<T> {
+  error: Vec<String>,
+  result: Option<T>,
+}
+
+#[derive(Deserialize)]
+struct KrakenAsset {
+  altname: String,
+  status: String,
+}
+
+#[derive(Deserialize)]
+struct KrakenMethod {
+  asset: String,
+  network: String,
+}
+
+#[derive(Deserialize)]
+struct CoinbaseCurrency {
+  id: String,
+  status: String,
+  #[serde(default)]
+  supported_networks: Vec<CoinbaseNetwork>,
+}
+
+#[derive(Deserialize)]
+struct CoinbaseNetwork {
+  id: String,
+  status: String,
+}
+
+#[derive(Deserialize)]
+struct OkxResponse {
+  code: String,
+  msg: String,
+  data: Vec<OkxCurrency>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxCurrency {
+  ccy: String,
+  chain: String,
+  can_dep: bool,
+  can_wd: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitResponse {
+  ret_code: i64,
+  ret_msg: String,
+  result: Option<BybitCoins>,
+}
+
+#[derive(Deserialize)]
+struct BybitCoins {
+  #[serde(default)]
+  rows: Vec<BybitCoin>,
+}
+
+#[derive(Deserialize)]
+struct BybitCoin {
+  coin: String,
+  chains: Vec<BybitChain>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitChain {
+  chain: String,
+  chain_deposit: String,
+  chain_withdraw: String,
+}
+
+impl<T> KrakenResponse<T> {
+  fn get_result(self) -> Result<T, NetworkError> {
+    match (self.error.is_empty(), self.result) {
+      (true, Some(result)) => Ok(result),
+      _ => Err(NetworkError::Rejected(self.error.join(", "))),
+    }
+  }
+}
+
+pub struct NetworkStatus {
+  http: reqwest::Client,
+  urls: HashMap<String, String>,
+  config: Config,
+}
+
+impl NetworkStatus {
+  pub fn new(config: &Config) -> Self {
+    Self::with_urls(config, HashMap::new())
+  }
+
+  pub fn with_urls(config: &Config, urls: HashMap<String, String>) -> Self {
+    Self {
+      http: reqwest::Client::new(),
+      urls,
+      config: config.clone(),
+    }
+  }
+
+  pub async fn get_asset_statuses(&self) -> Vec<(String, Result<Vec<AssetStatus>, NetworkError>)> {
+    let exchanges = Exchange::get_enabled(&self.config)
+      .into_iter()
+      .filter(|exchange| self.has_credentials(exchange))
+      .collect::<Vec<_>>();
+    let asset_statuses =
+      join_all(exchanges.iter().map(|exchange| self.get_statuses(exchange))).await;
+
+    exchanges
+      .iter()
+      .map(Exchange::get_name)
+      .zip(asset_statuses)
+      .collect()
+  }
+
+  pub async fn get_statuses(&self, exchange: &Exchange) -> Result<Vec<AssetStatus>, NetworkError> {
+    let venue = exchange.get_name();
+    let url = match (self.urls.get(&venue), exchange) {
+      (Some(url), _) => url.clone(),
+      (None, Exchange::CoinbaseEx) => COINBASE_URL.to_string(),
+      (None, _) => exchange.get_rest_url(),
+    };
+    let coins = match exchange {
+      Exchange::BinanceEx => self.get_binance_coins(&url).await?,
+      Exchange::BitkubEx => self.get_bitkub_coins(&url).await?,
+      #[cfg(feature = "ftx")]
+      Exchange::FtxEx => Vec::new(),
+      Exchange::KrakenEx => self.get_kraken_coins(&url).await?,
+      Exchange::CoinbaseEx => self.get_coinbase_coins(&url).await?,
+      Exchange::OkxEx => self.get_okx_coins(&url).await?,
+      Exchange::BybitEx => self.get_bybit_coins(&url).await?,
+    };
+    let timestamp = Utc::now().timestamp_millis();
+
+    Ok(
+      coins
+        .into_iter()
+        .map(|(coin, networks)| AssetStatus {
+          exchange: venue.clone(),
+          symbol: Aliases::get().get_key(&venue, &coin.to_lowercase()),
+          networks: networks
+            .into_iter()
+            .map(|network| Network {
+              name: Aliases::get().get_network(&venue, &network.name),
+              ..network
+            })
+            .collect(),
+          timestamp,
+        })
+        .collect(),
+    )
+  }
+
+  fn has_credentials(&self, exchange: &Exchange) -> bool {
+    match exchange {
+      Exchange::BinanceEx => !self.config.binance_api_key.is_empty(),
+      Exchange::BitkubEx => !self.config.bitkub_api_key.is_empty(),
+      #[cfg(feature = "ftx")]
+      Exchange::FtxEx => false,
+      Exchange::KrakenEx => !self.config.kraken_api_key.is_empty(),
+      Exchange::CoinbaseEx => true,
+      Exchange::OkxEx => !self.config.okx_api_key.is_empty(),
+      Exchange::BybitEx => !self.config.bybit_api_key.is_empty(),
+    }
+  }
+
+  async fn get_binance_coins(
+    &self,
+    url: &str,
+  ) -> Result<Vec<(String, Vec<Network>)>, NetworkError> {
+    let query = format!("timestamp={}", Utc::now().timestamp_millis());
+    let signature = hex::encode(get_signature(&self.config.binance_api_secret, &query)?);
+    let text = send(
+      self
+        .http
+        .get(format!("{url}{BINANCE_PATH}?{query}&signature={signature}"))
+        .header("X-MBX-APIKEY", &self.config.binance_api_key),
+    )
+    .await?;
+
+    Ok(
+      from_str::<Vec<BinanceCoin>>(&text)?
+        .into_iter()
+        .map(|coin| {
+          let networks = coin
+            .network_list
+            .into_iter()
+            .map(|network| Network {
+              name: network.network,
+              deposit: network.deposit_enable,
+              withdraw: network.withdraw_enable,
+            })
+            .collect();
+
+          (coin.coin, networks)
+        })
+        .collect(),
+    )
+  }
+
+  async fn get_bitkub_coins(&self, url: &str) -> Result<Vec<(String, Vec<Network>)>, NetworkError> {
+    let timestamp = Utc::now().timestamp_millis();
+    let signature = get_signature(
+      &self.config.bitkub_api_secret,
+      &format!("{timestamp}GET{BITKUB_PATH}"),
+    )?;
+    let text = send(
+      self
+        .http
+        .get(format!("{url}{BITKUB_PATH}"))
+        .header("X-BTK-APIKEY", &self.config.bitkub_api_key)
+        .header("X-BTK-TIMESTAMP", timestamp)
+        .header("X-BTK-SIGN", hex::encode(signature)),
+    )
+    .await?;
+    let response = from_str::<BitkubResponse>(&text)?;
+
+    let coins = match (response.code.as_str(), response.data) {
+      ("0", Some(coins)) => coins,
+      _ => return Err(NetworkError::Rejected(response.message)),
+    };
+
+    Ok(
+      coins
+        .items
+        .into_iter()
+        .map(|coin| {
+          let networks = coin
+            .networks
+            .into_iter()
+            .map(|network| Network {
+              name: network.network,
+              deposit: coin.deposit_enable && network.deposit_enable,
+              withdraw: coin.withdraw_enable && network.withdraw_enable,
+            })
+            .collect();
+
+          (coin.symbol, networks)
+        })
+        .collect(),
+    )
+  }
+
+  async fn get_kraken_coins(&self, url: &str) -> Result<Vec<(String, Vec<Network>)>, NetworkError> {
+    let assets = from_str::<KrakenResponse<HashMap<String, KrakenAsset>>>(
+      &get_text(&self.http, &format!("{url}{KRAKEN_ASSETS_PATH}")).await?,
+    )?
+    .get_result()?;
+    let nonce = Utc::now().timestamp_millis();
+    let body = format!("nonce={nonce}");
+    let secret = STANDARD
+      .decode(&self.config.kraken_api_secret)
+      .map_err(|_| NetworkError::Rejected("invalid kraken_api_secret".to_string()))?;
+    let mut mac = Hmac::<Sha512>::new_from_slice(&secret)
+      .map_err(|_| NetworkError::Rejected("invalid kraken_api_secret".to_string()))?;
+    mac.update(KRAKEN_METHODS_PATH.as_bytes());
+    mac.update(&Sha256::digest(format!("{nonce}{body}")));
+
+    let text = send(
+      self
+        .http
+        .post(format!("{url}{KRAKEN_METHODS_PATH}"))
+        .header("API-Key", &self.config.kraken_api_key)
+        .header("API-Sign", STANDARD.encode(mac.finalize().into_bytes()))
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(body),
+    )
+    .await?;
+    let methods = from_str::<KrakenResponse<Vec<KrakenMethod>>>(&text)?.get_result()?;
+
+    // Deposit methods can only be listed one asset at a time, so deposits are
+    // assumed on the withdrawal networks and gated by the asset status alone.
+    Ok(
+      assets
+        .into_iter()
+        .map(|(name, asset)| {
+          let deposit = matches!(asset.status.as_str(), "enabled" | "deposit_only");
+          let withdraw = matches!(asset.status.as_str(), "enabled" | "withdrawal_only");
+          let networks = methods
+            .iter()
+            .filter(|method| method.asset == asset.altname || method.asset == name)
+            .map(|method| Network {
+              name: method.network.clone(),
+              deposit,
+              withdraw,
+            })
+            .collect();
+
+          (asset.altname, networks)
+        })
+        .collect(),
+    )
+  }
+
+  async fn get_coinbase_coins(
+    &self,
+    url: &str,
+  ) -> Result<Vec<(String, Vec<Network>)>, NetworkError> {
+    let text = get_text(&self.http, &format!("{url}{COINBASE_PATH}")).await?;
+
+    // Coinbase reports a single status per network for both directions.
+    Ok(
+      from_str::<Vec<CoinbaseCurrency>>(&text)?
+        .into_iter()
+        .map(|currency| {
+          let online = currency.status == "online";
+          let networks = currency
+            .supported_networks
+            .into_iter()
+            .map(|network| {
+              let available = online && network.status == "online";
+
+              Network {
+                name: network.id,
+                deposit: available,
+                withdraw: available,
+              }
+            })
+            .collect();
+
+          (currency.id, networks)
+        })
+        .collect(),
+    )
+  }
+
+  async fn get_okx_coins(&self, url: &str) -> Result<Vec<(String, Vec<Network>)>, NetworkError> {
+    let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+    let signature = get_signature(
+      &self.config.okx_api_secret,
+      &format!("{timestamp}GET{OKX_PATH}"),
+    )?;
+    let text = send(
+      self
+        .http
+        .get(format!("{url}{OKX_PATH}"))
+        .header("OK-ACCESS-KEY", &self.config.okx_api_key)
+        .header("OK-ACCESS-SIGN", STANDARD.encode(signature))
+        .header("OK-ACCESS-TIMESTAMP", timestamp)
+        .header("OK-ACCESS-PASSPHRASE", &self.config.okx_api_passphrase),
+    )
+    .await?;
+    let response = from_str::<OkxResponse>(&text)?;
+
+    if response.code != "0" {
+      return Err(NetworkError::Rejected(response.msg));
+    }
+
+    let mut coins = BTreeMap::<String, Vec<Network>>::new();
+
+    // Chains are reported per row as "<ccy>-<chain>", e.g. "USDT-TRC20".
+    for currency in response.data {
+      let prefix = format!("{}-", currency.ccy);
+
+      coins.entry(currency.ccy).or_default().push(Network {
+        name: currency
+          .chain
+          .strip_prefix(&prefix)
+          .unwrap_or(&currency.chain)
+          .to_string(),
+        deposit: currency.can_dep,
+        withdraw: currency.can_wd,
+      });
+    }
+
+    Ok(coins.into_iter().collect())
+  }
+
+  async fn get_bybit_coins(&self, url: &str) -> Result<Vec<(String, Vec<Network>)>, NetworkError> {
+    let timestamp = Utc::now().timestamp_millis();
+    let key = &self.config.bybit_api_key;
+    let signature = get_signature(
+      &self.config.bybit_api_secret,
+      &format!("{timestamp}{key}{BYBIT_RECV_WINDOW}"),
+    )?;
+    let text = send(
+      self
+        .http
+        .get(format!("{url}{BYBIT_PATH}"))
+        .header("X-BAPI-API-KEY", key)
+        .header("X-BAPI-TIMESTAMP", timestamp)
+        .header("X-BAPI-RECV-WINDOW", BYBIT_RECV_WINDOW)
+        .header("X-BAPI-SIGN", hex::encode(signature)),
+    )
+    .await?;
+    let response = from_str::<BybitResponse>(&text)?;
+
+    let coins = match (response.ret_code, response.result) {
+      (0, Some(coins)) => coins,
+      _ => return Err(NetworkError::Rejected(response.ret_msg)),
+    };
+
+    Ok(
+      coins
+        .rows
+        .into_iter()
+        .map(|coin| {
+          let networks = coin
+            .chains
+            .into_iter()
+            .map(|chain| Network {
+              name: chain.chain,
+              deposit: chain.chain_deposit == "1",
+              withdraw: chain.chain_withdraw == "1",
+            })
+            .collect();
+
+          (coin.coin, networks)
+        })
+        .collect(),
+    )
+  }
+}
+
+async fn send(request: RequestBuilder) -> Result<String, NetworkError> {
+  Ok(request.send().await?.error_for_status()?.text().await?)
+}
+
+fn get_signature(secret: &str, message: &str) -> Result<Vec<u8>, NetworkError> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+    .map_err(|_| NetworkError::Rejected("invalid api secret".to_string()))?;
+  mac.update(message.as_bytes());
+
+  Ok(mac.finalize().into_bytes().to_vec())
+}
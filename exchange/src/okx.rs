@@ -0,0 +1,107 @@
+pub mod ticker;
+
+use crate::client::ExchangeClient;
+use crate::error::ParseError;
+use crate::event::MarketEvent;
+use crate::exchange::Exchange;
+use config::Config;
+use hodler::models::ticker::MarketTicker;
+use serde_json::{from_str, json};
+use std::time::Duration;
+use ticker::{OkxFrame, OkxTicker};
+use tokio_tungstenite::tungstenite::Message;
+
+// OKX rejects subscribe requests over 64 KB and counts every request against
+// a per-connection hourly limit, so instruments are sent a few dozen at a time
+// rather than one frame each or all in a single frame.
+const SUBSCRIPTION_BATCH_SIZE: usize = 20;
+
+#[derive(Clone)]
+pub struct Client {
+  pub name: Exchange,
+  ticker_url: String,
+  tickers: Vec<String>,
+}
+
+impl Client {
+  pub fn new() -> Self {
+    Self::with_config(&Config::default())
+  }
+
+  pub fn with_config(config: &Config) -> Self {
+    let name = Exchange::OkxEx;
+    let tickers = name.get_tickers(&name.get_symbols(config));
+
+    Self {
+      ticker_url: name.get_ticker_url_for(&tickers),
+      tickers,
+      name,
+    }
+  }
+}
+
+impl Default for Client {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ExchangeClient for Client {
+  fn name(&self) -> Exchange {
+    self.name.clone()
+  }
+
+  fn ticker_url(&self) -> String {
+    self.ticker_url.clone()
+  }
+
+  fn subscriptions(&self) -> Vec<String> {
+    self
+      .tickers
+      .chunks(SUBSCRIPTION_BATCH_SIZE)
+      .map(|tickers| {
+        let args = tickers
+          .iter()
+          .map(|ticker| json!({ "channel": "tickers", "instId": ticker }))
+          .collect::<Vec<_>>();
+
+        json!({ "op": "subscribe", "args": args }).to_string()
+      })
+      .collect()
+  }
+
+  fn ping_interval(&self) -> Option<Duration> {
+    Some(Duration::from_secs(25))
+  }
+
+  fn ping(&self) -> Message {
+    Message::Text("ping".into())
+  }
+
+  fn decode(&self, text: &str) -> Vec<Result<MarketEvent, ParseError>> {
+    if text == "pong" {
+      return Vec::new();
+    }
+
+    let frame = match from_str::<OkxFrame>(text) {
+      Ok(frame) => frame,
+      Err(err) => return vec![Err(err.into())],
+    };
+
+    match (frame.event.as_deref(), frame.arg) {
+      (Some("error"), _) => vec![Err(ParseError::SubscriptionRejected(text.to_string()))],
+      (Some(_), _) => vec![Err(ParseError::SubscriptionAck(text.to_string()))],
+      (None, Some(arg)) if arg.channel == "tickers" => match from_str::<OkxTicker>(text) {
+        Ok(ticker) => ticker
+          .into_tickers()
+          .into_iter()
+          .map(|t| t.map(MarketTicker::from).map(MarketEvent::from))
+          .collect(),
+        Err(err) => vec![Err(err.into())],
+      },
+      (None, arg) => vec![Err(ParseError::UnknownStream(
+        arg.map(|arg| arg.channel).unwrap_or_default(),
+      ))],
+    }
+  }
+}
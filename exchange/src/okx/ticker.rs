@@ -0,0 +1,92 @@
+use crate::error::{parse_number, ParseError};
+use crate::exchange::Exchange;
+use chrono::Utc;
+use hodler::models::ticker::MarketTicker;
+use serde::Deserialize;
+
+#[derive(Debug)]
+pub struct Ticker {
+  pub ask_price: f32,
+  pub ask_quantity: f32,
+  pub bid_price: f32,
+  pub bid_quantity: f32,
+  pub ticker_name: String,
+  pub change: Option<f32>,
+  pub volume: f32,
+  pub timestamp: i64,
+}
+
+#[derive(Deserialize)]
+pub struct OkxFrame {
+  pub event: Option<String>,
+  pub arg: Option<Arg>,
+}
+
+#[derive(Deserialize)]
+pub struct Arg {
+  pub channel: String,
+}
+
+#[derive(Deserialize)]
+pub struct OkxTicker {
+  data: Vec<Data>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct Data {
+  instId: String,
+  askPx: String,
+  askSz: String,
+  bidPx: String,
+  bidSz: String,
+  last: String,
+  open24h: String,
+  vol24h: String,
+  ts: String,
+}
+
+impl OkxTicker {
+  pub fn into_tickers(self) -> Vec<Result<Ticker, ParseError>> {
+    self
+      .data
+      .into_iter()
+      .map(|ticker| {
+        let last = parse_number("last", &ticker.last)?;
+        let open = parse_number("open24h", &ticker.open24h)?;
+
+        Ok(Ticker {
+          ask_price: parse_number("askPx", &ticker.askPx)?,
+          ask_quantity: parse_number("askSz", &ticker.askSz)?,
+          bid_price: parse_number("bidPx", &ticker.bidPx)?,
+          bid_quantity: parse_number("bidSz", &ticker.bidSz)?,
+          ticker_name: ticker.instId.to_lowercase(),
+          change: (open > 0.0).then(|| (last / open - 1.0) * 100.0),
+          volume: parse_number("vol24h", &ticker.vol24h)?,
+          timestamp: ticker.ts.parse().map_err(|_| ParseError::InvalidNumber {
+            field: "ts",
+            raw: ticker.ts.clone(),
+          })?,
+        })
+      })
+      .collect()
+  }
+}
+
+impl From<Ticker> for MarketTicker {
+  fn from(ticker: Ticker) -> Self {
+    Self {
+      exchange: Exchange::OkxEx.get_name(),
+      symbol: Exchange::OkxEx.get_key(ticker.ticker_name.clone()),
+      ticker_name: ticker.ticker_name,
+      ask_price: ticker.ask_price,
+      ask_quantity: Some(ticker.ask_quantity),
+      bid_price: ticker.bid_price,
+      bid_quantity: Some(ticker.bid_quantity),
+      volume: Some(ticker.volume),
+      percent_change: ticker.change,
+      exchange_timestamp: Some(ticker.timestamp),
+      received_at: Utc::now().timestamp_millis(),
+    }
+  }
+}
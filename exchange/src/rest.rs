@@ -0,0 +1,5 @@
+use reqwest::{Client, Error};
+
+pub async fn get_text(http: &Client, url: &str) -> Result<String, Error> {
+  http.get(url).send().await?.error_for_status()?.text().await
+}
@@ -0,0 +1,219 @@
+use crate::client::{ExchangeClient, Shard, WsStream};
+use crate::error::ParseError;
+use crate::event::MarketEvent;
+use config::Config;
+use futures_util::future::{join_all, pending};
+use futures_util::{SinkExt, StreamExt};
+use log::{debug, error, warn};
+use rand::Rng;
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::time::{interval, interval_at, sleep, sleep_until, Instant, Interval};
+use tokio::{join, select};
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+
+type EventSender = UnboundedSender<Result<MarketEvent, ParseError>>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Backoff {
+  pub initial_delay: Duration,
+  pub max_delay: Duration,
+  pub multiplier: f64,
+  pub jitter: f64,
+  pub max_attempts: Option<u32>,
+}
+
+impl Backoff {
+  pub fn get_delay(&self, attempt: u32) -> Duration {
+    let delay = self.initial_delay.as_secs_f64() * self.multiplier.powi(attempt as i32);
+    let delay = delay.min(self.max_delay.as_secs_f64());
+    let jitter = match self.jitter > 0.0 {
+      true => delay * rand::thread_rng().gen_range(-self.jitter..=self.jitter),
+      false => 0.0,
+    };
+
+    Duration::from_secs_f64((delay + jitter).max(0.0))
+  }
+
+  pub fn is_exhausted(&self, attempt: u32) -> bool {
+    match self.max_attempts {
+      Some(max_attempts) => attempt >= max_attempts,
+      None => false,
+    }
+  }
+}
+
+impl Default for Backoff {
+  fn default() -> Self {
+    Self::from(&Config::default())
+  }
+}
+
+impl From<&Config> for Backoff {
+  fn from(config: &Config) -> Self {
+    Self {
+      initial_delay: Duration::from_millis(config.reconnect_initial_delay_ms),
+      max_delay: Duration::from_millis(config.reconnect_max_delay_ms),
+      multiplier: config.reconnect_multiplier,
+      jitter: config.reconnect_jitter,
+      max_attempts: config.reconnect_max_attempts,
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum Disconnect {
+  ConnectFailed(WsError),
+  StreamFailed(WsError),
+  Closed(Option<CloseFrame>),
+  StreamEnded,
+  Idle(Duration),
+}
+
+pub struct Supervisor {
+  client: Box<dyn ExchangeClient>,
+  backoff: Backoff,
+  idle_timeout: Duration,
+}
+
+impl Supervisor {
+  pub fn new(client: Box<dyn ExchangeClient>, backoff: Backoff, idle_timeout: Duration) -> Self {
+    Self {
+      client,
+      backoff,
+      idle_timeout,
+    }
+  }
+
+  pub async fn run<F>(&self, mut on_event: F) -> Vec<Disconnect>
+  where
+    F: FnMut(Result<MarketEvent, ParseError>),
+  {
+    let (sender, mut receiver) = unbounded_channel();
+    let shards = self
+      .client
+      .get_shards()
+      .into_iter()
+      .enumerate()
+      .map(|(index, shard)| self.supervise(index, shard, sender.clone()))
+      .collect::<Vec<_>>();
+    let feeds = async {
+      select! {
+        disconnects = join_all(shards) => disconnects,
+        never = self.poll(sender) => match never {},
+      }
+    };
+
+    let (disconnects, _) = join!(feeds, async {
+      while let Some(event) = receiver.recv().await {
+        on_event(event);
+      }
+    });
+
+    disconnects
+  }
+
+  async fn poll(&self, sender: EventSender) -> Infallible {
+    let name = self.client.name().get_name();
+    let mut poll_interval = self.client.poll_interval().map(interval);
+
+    loop {
+      tick(&mut poll_interval).await;
+      self.client.poll().await.into_iter().for_each(|event| {
+        if let Err(err) = &event {
+          error!(target: &name, "{err}");
+        }
+
+        _ = sender.send(event);
+      });
+    }
+  }
+
+  async fn supervise(&self, index: usize, shard: Shard, sender: EventSender) -> Disconnect {
+    let name = self.client.name().get_name();
+    let mut attempt = 0;
+
+    loop {
+      let (disconnect, received) = match self.client.connect_ws(&shard).await {
+        Ok(stream) => self.read(stream, &sender).await,
+        Err(err) => (Disconnect::ConnectFailed(err), false),
+      };
+
+      attempt = if received { 0 } else { attempt + 1 };
+
+      if self.backoff.is_exhausted(attempt) {
+        error!(target: &name, "shard {index}: {disconnect:?}, giving up after {attempt} attempts");
+        return disconnect;
+      }
+
+      let delay = self.backoff.get_delay(attempt.saturating_sub(1));
+      warn!(target: &name, "shard {index}: {disconnect:?}, reconnecting in {delay:?}");
+      sleep(delay).await;
+    }
+  }
+
+  async fn read(&self, stream: WsStream, sender: &EventSender) -> (Disconnect, bool) {
+    let name = self.client.name().get_name();
+    let idle_timeout = self.idle_timeout;
+    let mut ping_interval = self
+      .client
+      .ping_interval()
+      .map(|period| interval_at(Instant::now() + period, period));
+    let mut last_message_at = Instant::now();
+    let mut received = false;
+    let (mut write, mut read) = stream.split();
+
+    loop {
+      let message = select! {
+        message = read.next() => message,
+        _ = sleep_until(last_message_at + idle_timeout) => {
+          return (Disconnect::Idle(idle_timeout), received);
+        }
+        _ = tick(&mut ping_interval) => {
+          match write.send(self.client.ping()).await {
+            Ok(_) => continue,
+            Err(err) => return (Disconnect::StreamFailed(err), received),
+          }
+        }
+      };
+
+      let text = match message {
+        Some(Ok(Message::Text(text))) => text,
+        Some(Ok(Message::Close(frame))) => return (Disconnect::Closed(frame), received),
+        Some(Ok(message)) => {
+          debug!(target: &name, "{message:?}");
+          continue;
+        }
+        Some(Err(err)) => return (Disconnect::StreamFailed(err), received),
+        None => return (Disconnect::StreamEnded, received),
+      };
+
+      last_message_at = Instant::now();
+      received = true;
+      self
+        .client
+        .decode(&text)
+        .into_iter()
+        .for_each(|event| match event {
+          Ok(event) => _ = sender.send(Ok(event)),
+          Err(ParseError::SubscriptionAck(ack)) => debug!(target: &name, "{ack}"),
+          Err(ParseError::SubscriptionRejected(ack)) => warn!(target: &name, "{ack}"),
+          Err(err) => {
+            error!(target: &name, "{err}: {text}");
+            _ = sender.send(Err(err));
+          }
+        });
+    }
+  }
+}
+
+async fn tick(interval: &mut Option<Interval>) {
+  match interval {
+    Some(interval) => {
+      interval.tick().await;
+    }
+    None => pending().await,
+  }
+}
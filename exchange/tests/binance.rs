@@ -0,0 +1,225 @@
+use config::Config;
+use exchange::binance::depth::{Depth, DepthSnapshot, DepthUpdate, MAX_BUFFERED_UPDATES};
+use exchange::binance::Client;
+use exchange::client::ExchangeClient;
+use exchange::error::ParseError;
+use hodler::models::book::{Level, OrderBook};
+use std::collections::BTreeMap;
+
+fn get_update(first_update_id: u64, final_update_id: u64, ask: (f32, f32)) -> DepthUpdate {
+  DepthUpdate {
+    ticker_name: "ethusdt".to_string(),
+    first_update_id,
+    final_update_id,
+    asks: vec![Level {
+      price: ask.0,
+      quantity: ask.1,
+    }],
+    bids: Vec::new(),
+    timestamp: final_update_id as i64,
+  }
+}
+
+fn get_snapshot(last_update_id: u64) -> DepthSnapshot {
+  DepthSnapshot {
+    last_update_id,
+    asks: vec![
+      Level {
+        price: 2000.0,
+        quantity: 1.0,
+      },
+      Level {
+        price: 2001.0,
+        quantity: 2.0,
+      },
+    ],
+    bids: vec![Level {
+      price: 1999.0,
+      quantity: 3.0,
+    }],
+  }
+}
+
+fn get_asks(order_book: &OrderBook) -> Vec<(f32, f32)> {
+  order_book
+    .asks
+    .iter()
+    .map(|level| (level.price, level.quantity))
+    .collect()
+}
+
+fn get_synced(last_update_id: u64) -> Depth {
+  let mut depth = Depth::Buffering(Vec::new());
+  depth.sync("ethusdt".to_string(), get_snapshot(last_update_id), 10);
+  depth
+}
+
+#[test]
+fn syncs_snapshot_with_buffered_updates() {
+  let mut depth = Depth::Buffering(Vec::new());
+
+  assert!(depth.apply(get_update(1, 5, (2000.0, 9.0)), 10).is_none());
+  assert!(depth.apply(get_update(6, 8, (2000.5, 1.0)), 10).is_none());
+  assert!(depth.apply(get_update(9, 10, (2001.0, 0.0)), 10).is_none());
+
+  let order_book = depth
+    .sync("ethusdt".to_string(), get_snapshot(7), 10)
+    .unwrap();
+
+  assert!(depth.is_synced());
+  assert_eq!(order_book.exchange, "binance");
+  assert_eq!(order_book.symbol, "eth");
+  assert_eq!(get_asks(&order_book), vec![(2000.0, 1.0), (2000.5, 1.0)]);
+  assert_eq!(order_book.timestamp, 10);
+}
+
+#[test]
+fn rebuffers_when_snapshot_predates_buffered_updates() {
+  let mut depth = Depth::Buffering(Vec::new());
+  depth.apply(get_update(20, 25, (2000.5, 1.0)), 10);
+
+  assert!(depth
+    .sync("ethusdt".to_string(), get_snapshot(10), 10)
+    .is_none());
+  assert!(matches!(&depth, Depth::Buffering(updates) if updates.len() == 1));
+  assert!(depth
+    .sync("ethusdt".to_string(), get_snapshot(22), 10)
+    .is_some());
+}
+
+#[test]
+fn ignores_stale_updates() {
+  let mut depth = get_synced(10);
+
+  assert!(depth.apply(get_update(5, 10, (2000.0, 0.0)), 10).is_none());
+
+  let order_book = depth.apply(get_update(9, 12, (2000.0, 4.0)), 10).unwrap();
+
+  assert_eq!(get_asks(&order_book), vec![(2000.0, 4.0), (2001.0, 2.0)]);
+}
+
+#[test]
+fn resyncs_after_sequence_gap() {
+  let mut depth = get_synced(10);
+
+  assert!(depth.apply(get_update(11, 11, (2000.0, 5.0)), 10).is_some());
+  assert!(depth.apply(get_update(14, 15, (2000.0, 6.0)), 10).is_none());
+  assert!(!depth.is_synced());
+  assert!(matches!(&depth, Depth::Buffering(updates) if updates[0].first_update_id == 14));
+}
+
+#[test]
+fn truncates_books_to_depth() {
+  let mut depth = get_synced(10);
+  let order_book = depth.apply(get_update(11, 11, (1999.5, 1.0)), 2).unwrap();
+
+  assert_eq!(get_asks(&order_book), vec![(1999.5, 1.0), (2000.0, 1.0)]);
+}
+
+#[test]
+fn caps_buffered_updates() {
+  let mut depth = Depth::Buffering(Vec::new());
+
+  (0..MAX_BUFFERED_UPDATES as u64 + 50).for_each(|id| {
+    depth.apply(get_update(id, id, (2000.0, 1.0)), 10);
+  });
+
+  match &depth {
+    Depth::Buffering(updates) => {
+      assert_eq!(updates.len(), MAX_BUFFERED_UPDATES);
+      assert_eq!(updates[0].first_update_id, 50);
+    }
+    depth => panic!("unexpected {depth:?}"),
+  }
+}
+
+#[test]
+fn subscribes_only_listed_triangle_pairs() {
+  let listing = [
+    "btc/usdt", "eth/usdt", "xrp/usdt", "eth/btc", "xrp/eth", "doge/btc",
+  ];
+  let config = Config {
+    symbols: vec!["eth".to_string(), "xrp".to_string()],
+    listings: BTreeMap::from([(
+      "binance".to_string(),
+      listing.iter().map(|pair| pair.to_string()).collect(),
+    )]),
+    order_books: false,
+    trades: false,
+    ..Config::default()
+  };
+  let ticker_url = Client::with_config(&config).get_shards()[0]
+    .ticker_url
+    .clone();
+  let streams = ticker_url.split_once("streams=").unwrap().1;
+
+  assert_eq!(
+    streams.split('/').collect::<Vec<_>>(),
+    vec![
+      "btcusdt@ticker",
+      "ethusdt@ticker",
+      "xrpusdt@ticker",
+      "ethbtc@ticker",
+      "xrpeth@ticker",
+    ]
+  );
+}
+
+#[test]
+fn subscribes_no_triangle_pairs_without_a_listing() {
+  let config = Config {
+    symbols: vec!["eth".to_string()],
+    order_books: false,
+    trades: false,
+    ..Config::default()
+  };
+  let ticker_url = Client::with_config(&config).get_shards()[0]
+    .ticker_url
+    .clone();
+
+  assert!(ticker_url.ends_with("streams=btcusdt@ticker/ethusdt@ticker"));
+}
+
+#[test]
+fn pairs_configured_triangle_quotes_without_discovery() {
+  let config = Config {
+    symbols: vec!["eth".to_string(), "xrp".to_string()],
+    symbol_discovery: false,
+    order_books: false,
+    trades: false,
+    ..Config::default()
+  };
+  let ticker_url = Client::with_config(&config).get_shards()[0]
+    .ticker_url
+    .clone();
+  let streams = ticker_url.split_once("streams=").unwrap().1;
+
+  assert_eq!(
+    streams.split('/').collect::<Vec<_>>(),
+    vec![
+      "btcusdt@ticker",
+      "ethusdt@ticker",
+      "xrpusdt@ticker",
+      "ethbtc@ticker",
+      "xrpbtc@ticker",
+      "xrpeth@ticker",
+    ]
+  );
+}
+
+#[test]
+fn reports_subscription_responses() {
+  let client = Client::new();
+  let ack = client.decode(include_str!("fixtures/binance/subscribe_ack.json"));
+  let rejected = client.decode(include_str!("fixtures/binance/subscribe_error.json"));
+
+  assert!(matches!(ack[..], [Err(ParseError::SubscriptionAck(_))]));
+  assert!(matches!(
+    rejected[..],
+    [Err(ParseError::SubscriptionRejected(_))]
+  ));
+  assert!(matches!(
+    client.decode(r#"{"result":null}"#)[..],
+    [Err(ParseError::InvalidFrame(_))]
+  ));
+}
@@ -0,0 +1,109 @@
+use config::Config;
+use exchange::bybit::Client;
+use exchange::client::ExchangeClient;
+use exchange::error::ParseError;
+use exchange::event::MarketEvent;
+use hodler::models::ticker::MarketTicker;
+use serde_json::Value;
+
+fn decode_ticker(client: &Client, text: &str) -> Option<MarketTicker> {
+  match client.decode(text).pop() {
+    Some(Ok(MarketEvent::Ticker(ticker))) => Some(ticker),
+    Some(event) => panic!("unexpected {event:?}"),
+    None => None,
+  }
+}
+
+#[test]
+fn quotes_top_of_book_with_ticker_stats() {
+  let client = Client::new();
+
+  assert!(decode_ticker(&client, include_str!("fixtures/bybit/ticker.json")).is_none());
+
+  let ticker = decode_ticker(&client, include_str!("fixtures/bybit/book_snapshot.json")).unwrap();
+
+  assert_eq!(ticker.exchange, "bybit");
+  assert_eq!(ticker.symbol, "btc");
+  assert_eq!(ticker.ticker_name, "btcusdt");
+  assert_eq!(ticker.ask_price, 26500.2);
+  assert_eq!(ticker.ask_quantity, Some(1.5));
+  assert_eq!(ticker.bid_price, 26499.9);
+  assert_eq!(ticker.bid_quantity, Some(0.8));
+  assert_eq!(ticker.volume, Some(9500.5));
+  assert!((ticker.percent_change.unwrap() - 1.92).abs() < 0.001);
+  assert_eq!(ticker.exchange_timestamp, Some(1695632672200));
+}
+
+#[test]
+fn waits_for_ticker_stats_before_quoting() {
+  let client = Client::new();
+
+  assert!(decode_ticker(&client, include_str!("fixtures/bybit/book_snapshot.json")).is_none());
+
+  let ticker = decode_ticker(&client, include_str!("fixtures/bybit/ticker.json")).unwrap();
+
+  assert_eq!(ticker.ask_price, 26500.2);
+  assert_eq!(ticker.bid_price, 26499.9);
+}
+
+#[test]
+fn applies_book_deltas_to_one_side() {
+  let client = Client::new();
+  client.decode(include_str!("fixtures/bybit/ticker.json"));
+  client.decode(include_str!("fixtures/bybit/book_snapshot.json"));
+
+  let ticker = decode_ticker(&client, include_str!("fixtures/bybit/book_delta.json")).unwrap();
+
+  assert_eq!(ticker.ask_price, 26500.4);
+  assert_eq!(ticker.ask_quantity, Some(0.3));
+  assert_eq!(ticker.bid_price, 26499.9);
+  assert_eq!(ticker.exchange_timestamp, Some(1695632672300));
+}
+
+#[test]
+fn reports_subscription_acks_and_ignores_pong() {
+  let client = Client::new();
+  let ack = client.decode(include_str!("fixtures/bybit/subscribe_ack.json"));
+  let rejected = client.decode(include_str!("fixtures/bybit/subscribe_error.json"));
+
+  assert!(matches!(ack[..], [Err(ParseError::SubscriptionAck(_))]));
+  assert!(matches!(
+    rejected[..],
+    [Err(ParseError::SubscriptionRejected(_))]
+  ));
+  assert!(client
+    .decode(include_str!("fixtures/bybit/pong.json"))
+    .is_empty());
+}
+
+#[test]
+fn rejects_unknown_topics_and_invalid_books() {
+  let client = Client::new();
+  let trade = client.decode(include_str!("fixtures/bybit/trade.json"));
+  let invalid = client.decode(include_str!("fixtures/bybit/book_invalid.json"));
+
+  assert!(
+    matches!(&trade[..], [Err(ParseError::UnknownStream(topic))] if topic == "publicTrade.BTCUSDT")
+  );
+  assert!(matches!(
+    invalid[..],
+    [Err(ParseError::InvalidNumber { field: "a", .. })]
+  ));
+}
+
+#[test]
+fn subscribes_to_tickers_and_top_of_book() {
+  let config = Config {
+    symbols: vec!["btc".to_string(), "eth".to_string(), "ada".to_string()],
+    ..Config::default()
+  };
+  let subscriptions = Client::with_config(&config).subscriptions();
+  let batch = serde_json::from_str::<Value>(&subscriptions[0]).unwrap();
+
+  assert_eq!(subscriptions.len(), 1);
+  assert_eq!(batch["op"], "subscribe");
+  assert_eq!(batch["args"][0], "tickers.BTCUSDT");
+  assert_eq!(batch["args"][1], "orderbook.1.BTCUSDT");
+  assert_eq!(batch["args"].as_array().unwrap().len(), 6);
+  assert_eq!(Client::new().subscriptions().len(), 16);
+}
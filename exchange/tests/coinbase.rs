@@ -0,0 +1,102 @@
+use config::Config;
+use exchange::client::ExchangeClient;
+use exchange::coinbase::Client;
+use exchange::error::ParseError;
+use exchange::event::MarketEvent;
+use hodler::models::ticker::MarketTicker;
+use serde_json::Value;
+
+fn decode_tickers(text: &str) -> Vec<MarketTicker> {
+  Client::new()
+    .decode(text)
+    .into_iter()
+    .map(|event| match event.unwrap() {
+      MarketEvent::Ticker(ticker) => ticker,
+      event => panic!("unexpected {event:?}"),
+    })
+    .collect()
+}
+
+#[test]
+fn decodes_every_ticker_in_a_frame() {
+  let tickers = decode_tickers(include_str!("fixtures/coinbase/ticker.json"));
+
+  assert_eq!(tickers.len(), 2);
+  assert_eq!(tickers[0].exchange, "coinbase");
+  assert_eq!(tickers[0].symbol, "btc");
+  assert_eq!(tickers[0].ticker_name, "btc-usd");
+  assert_eq!(tickers[0].ask_price, 26500.2);
+  assert_eq!(tickers[0].ask_quantity, Some(1.5));
+  assert_eq!(tickers[0].bid_price, 26500.1);
+  assert_eq!(tickers[0].bid_quantity, Some(0.8));
+  assert_eq!(tickers[0].volume, Some(9500.5));
+  assert_eq!(tickers[0].percent_change, Some(1.92));
+  assert_eq!(tickers[0].exchange_timestamp, Some(1695630672153));
+  assert_eq!(tickers[1].symbol, "eth");
+  assert_eq!(tickers[1].percent_change, Some(-0.75));
+}
+
+#[test]
+fn ignores_heartbeats() {
+  assert!(Client::new()
+    .decode(include_str!("fixtures/coinbase/heartbeat.json"))
+    .is_empty());
+}
+
+#[test]
+fn reports_subscription_acks() {
+  let client = Client::new();
+  let ack = client.decode(include_str!("fixtures/coinbase/subscribe_ack.json"));
+  let rejected = client.decode(include_str!("fixtures/coinbase/subscribe_error.json"));
+
+  assert!(matches!(ack[..], [Err(ParseError::SubscriptionAck(_))]));
+  assert!(matches!(
+    rejected[..],
+    [Err(ParseError::SubscriptionRejected(_))]
+  ));
+}
+
+#[test]
+fn rejects_unknown_channels_and_invalid_tickers() {
+  let client = Client::new();
+  let level2 = client.decode(include_str!("fixtures/coinbase/level2.json"));
+  let invalid = client.decode(include_str!("fixtures/coinbase/ticker_invalid.json"));
+
+  assert!(matches!(&level2[..], [Err(ParseError::UnknownStream(channel))] if channel == "l2_data"));
+  assert!(matches!(
+    invalid[..],
+    [Err(ParseError::InvalidNumber {
+      field: "best_ask",
+      ..
+    })]
+  ));
+  assert!(matches!(
+    client.decode("{")[..],
+    [Err(ParseError::InvalidFrame(_))]
+  ));
+}
+
+#[test]
+fn subscribes_to_tickers_and_heartbeats() {
+  let config = Config {
+    symbols: vec!["eth".to_string(), "xrp".to_string()],
+    ..Config::default()
+  };
+  let subscriptions = Client::with_config(&config)
+    .subscriptions()
+    .iter()
+    .map(|subscription| serde_json::from_str::<Value>(subscription).unwrap())
+    .collect::<Vec<_>>();
+
+  assert_eq!(subscriptions.len(), 2);
+  assert_eq!(subscriptions[0]["type"], "subscribe");
+  assert_eq!(subscriptions[0]["channel"], "ticker");
+  assert_eq!(subscriptions[1]["channel"], "heartbeats");
+
+  for subscription in subscriptions {
+    assert_eq!(
+      subscription["product_ids"],
+      serde_json::json!(["BTC-USD", "ETH-USD", "XRP-USD"])
+    );
+  }
+}
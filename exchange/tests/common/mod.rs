@@ -0,0 +1,40 @@
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::spawn;
+
+pub type Requests = Arc<Mutex<Vec<String>>>;
+
+// Routes match the full request target, or its path when the route has no query.
+pub async fn serve(routes: Vec<(&'static str, &'static str)>) -> (String, Requests) {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let url = format!("http://{}", listener.local_addr().unwrap());
+  let requests = Requests::default();
+  let received = requests.clone();
+
+  spawn(async move {
+    loop {
+      let (mut stream, _) = listener.accept().await.unwrap();
+      let mut request = vec![0; 4096];
+      let read = stream.read(&mut request).await.unwrap();
+      let request = String::from_utf8_lossy(&request[..read]).to_string();
+      let target = request.split(' ').nth(1).unwrap_or_default().to_string();
+      let path = target.split('?').next().unwrap_or_default();
+      let response = match routes
+        .iter()
+        .find(|(route, _)| target == *route || path == *route)
+      {
+        Some((_, body)) => format!(
+          "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+          body.len()
+        ),
+        None => "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_string(),
+      };
+
+      received.lock().unwrap().push(request);
+      stream.write_all(response.as_bytes()).await.unwrap();
+    }
+  });
+
+  (url, requests)
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use common::serve;
+use config::Config;
+use exchange::discovery::Discovery;
+use exchange::error::DiscoveryError;
+use exchange::exchange::Exchange;
+use std::collections::HashMap;
+
+async fn get_discovery() -> Discovery {
+  let routes = [
+    (
+      "binance",
+      "/api/v3/exchangeInfo",
+      include_str!("fixtures/discovery/binance_exchange_info.json"),
+    ),
+    (
+      "bitkub",
+      "/api/market/symbols",
+      include_str!("fixtures/discovery/bitkub_symbols.json"),
+    ),
+    (
+      "kraken",
+      "/0/public/AssetPairs",
+      include_str!("fixtures/discovery/kraken_asset_pairs.json"),
+    ),
+    (
+      "coinbase",
+      "/api/v3/brokerage/market/products?product_type=SPOT",
+      include_str!("fixtures/discovery/coinbase_products.json"),
+    ),
+    (
+      "okx",
+      "/api/v5/public/instruments?instType=SPOT",
+      include_str!("fixtures/discovery/okx_instruments.json"),
+    ),
+    (
+      "bybit",
+      "/v5/market/instruments-info?category=spot",
+      include_str!("fixtures/discovery/bybit_instruments.json"),
+    ),
+  ];
+  let mut urls = HashMap::new();
+
+  for (venue, route, body) in routes {
+    urls.insert(venue.to_string(), serve(vec![(route, body)]).await.0);
+  }
+
+  Discovery::with_urls(urls)
+}
+
+#[tokio::test]
+async fn discovers_assets_tradable_on_every_venue() {
+  let symbols = get_discovery().await.get_symbols().await.unwrap();
+
+  assert_eq!(symbols, vec!["btc", "eth", "luna", "powr"]);
+}
+
+#[tokio::test]
+async fn builds_subscriptions_from_discovered_symbols() {
+  let symbols = get_discovery().await.get_symbols().await.unwrap();
+
+  assert_eq!(
+    Exchange::BinanceEx.get_tickers(&symbols),
+    vec![
+      "btcusdt@ticker",
+      "ethusdt@ticker",
+      "luncusdt@ticker",
+      "powrusdt@ticker"
+    ]
+  );
+  assert_eq!(
+    Exchange::BitkubEx.get_tickers(&symbols),
+    vec![
+      "market.ticker.thb_btc",
+      "market.ticker.thb_eth",
+      "market.ticker.thb_luna",
+      "market.ticker.thb_pow"
+    ]
+  );
+}
+
+#[tokio::test]
+async fn lists_tradable_pairs_per_venue() {
+  let discovery = get_discovery().await;
+  let listings = discovery.get_listings(&Exchange::get_all()).await;
+
+  assert_eq!(
+    listings["binance"],
+    vec![
+      "btc/usdt",
+      "doge/usdt",
+      "eth/btc",
+      "eth/usdt",
+      "luna/usdt",
+      "luna2/usdt",
+      "powr/usdt"
+    ]
+  );
+  assert_eq!(listings["kraken"], vec!["btc/usd", "eth/btc", "eth/usd"]);
+  assert_eq!(listings["coinbase"], vec!["btc/usd", "eth/btc", "powr/usd"]);
+  assert_eq!(listings["okx"], vec!["btc/usdt", "luna/usdt"]);
+  assert_eq!(listings["bybit"], vec!["btc/usdt", "eth/usdt"]);
+}
+
+#[tokio::test]
+async fn subscribes_each_venue_to_its_listed_symbols() {
+  let discovery = get_discovery().await;
+  let mut config = Config {
+    symbols: discovery.get_symbols().await.unwrap(),
+    ..Config::default()
+  };
+  config.listings = discovery.get_listings(&Exchange::get_all()).await;
+
+  assert_eq!(
+    Exchange::BitkubEx.get_symbols(&config),
+    vec!["btc", "eth", "luna", "powr"]
+  );
+  assert_eq!(Exchange::KrakenEx.get_symbols(&config), vec!["btc", "eth"]);
+  assert_eq!(
+    Exchange::CoinbaseEx.get_symbols(&config),
+    vec!["btc", "powr"]
+  );
+  assert_eq!(Exchange::OkxEx.get_symbols(&config), vec!["btc", "luna"]);
+  assert_eq!(Exchange::BybitEx.get_symbols(&config), vec!["btc", "eth"]);
+}
+
+#[test]
+fn always_subscribes_the_reference_currency() {
+  let config = Config {
+    symbols: vec!["eth".to_string()],
+    ..Config::default()
+  };
+
+  assert_eq!(Exchange::OkxEx.get_symbols(&config), vec!["btc", "eth"]);
+}
+
+#[tokio::test]
+async fn skips_unavailable_listings() {
+  let urls = HashMap::from([("kraken".to_string(), serve(Vec::new()).await.0)]);
+  let discovery = Discovery::with_urls(urls);
+  let config = Config {
+    symbols: vec!["btc".to_string(), "eth".to_string()],
+    listings: discovery.get_listings(&[Exchange::KrakenEx]).await,
+    ..Config::default()
+  };
+
+  assert!(config.listings.is_empty());
+  assert_eq!(Exchange::KrakenEx.get_symbols(&config), vec!["btc", "eth"]);
+}
+
+#[tokio::test]
+async fn fails_when_a_venue_is_unavailable() {
+  let (binance_url, _) = serve(vec![(
+    "/api/v3/exchangeInfo",
+    include_str!("fixtures/discovery/binance_exchange_info.json"),
+  )])
+  .await;
+  let (bitkub_url, _) = serve(Vec::new()).await;
+  let discovery = Discovery::with_urls(HashMap::from([
+    ("binance".to_string(), binance_url),
+    ("bitkub".to_string(), bitkub_url),
+  ]));
+
+  assert!(matches!(
+    discovery.get_symbols().await,
+    Err(DiscoveryError::Request(_))
+  ));
+}
+
+#[tokio::test]
+async fn fails_when_no_symbols_overlap() {
+  let (binance_url, _) = serve(vec![(
+    "/api/v3/exchangeInfo",
+    include_str!("fixtures/discovery/binance_exchange_info.json"),
+  )])
+  .await;
+  let (bitkub_url, _) = serve(vec![("/api/market/symbols", "{\"error\":0,\"result\":[]}")]).await;
+  let discovery = Discovery::with_urls(HashMap::from([
+    ("binance".to_string(), binance_url),
+    ("bitkub".to_string(), bitkub_url),
+  ]));
+
+  assert!(matches!(
+    discovery.get_symbols().await,
+    Err(DiscoveryError::NoSymbols)
+  ));
+}
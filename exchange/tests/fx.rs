@@ -0,0 +1,50 @@
+mod common;
+
+use common::serve;
+use exchange::error::FxError;
+use exchange::fx::FxSource;
+
+#[tokio::test]
+async fn reads_the_usd_thb_rate() {
+  let (url, _) = serve(vec![(
+    "/v6/latest/USD",
+    "{\"result\":\"success\",\"base_code\":\"USD\",\"rates\":{\"USD\":1,\"EUR\":0.92,\"THB\":36.25}}",
+  )])
+  .await;
+  let url = format!("{url}/v6/latest/USD");
+  let fx_rate = FxSource::with_url(url.clone()).get_rate().await.unwrap();
+
+  assert_eq!(fx_rate.rate, 36.25);
+  assert_eq!(fx_rate.source, url);
+  assert!(fx_rate.timestamp > 0);
+}
+
+#[tokio::test]
+async fn fails_without_a_thb_rate() {
+  let (url, _) = serve(vec![("/rates", "{\"rates\":{\"USD\":1,\"THB\":0}}")]).await;
+
+  assert!(matches!(
+    FxSource::with_url(format!("{url}/rates")).get_rate().await,
+    Err(FxError::MissingRate)
+  ));
+}
+
+#[tokio::test]
+async fn fails_on_invalid_responses() {
+  let (url, _) = serve(vec![("/rates", "{\"error\":\"quota exceeded\"}")]).await;
+
+  assert!(matches!(
+    FxSource::with_url(format!("{url}/rates")).get_rate().await,
+    Err(FxError::InvalidResponse(_))
+  ));
+}
+
+#[tokio::test]
+async fn fails_when_unavailable() {
+  let (url, _) = serve(Vec::new()).await;
+
+  assert!(matches!(
+    FxSource::with_url(format!("{url}/rates")).get_rate().await,
+    Err(FxError::Request(_))
+  ));
+}
@@ -0,0 +1,87 @@
+use exchange::client::ExchangeClient;
+use exchange::error::ParseError;
+use exchange::event::MarketEvent;
+use exchange::kraken::Client;
+use hodler::models::ticker::MarketTicker;
+
+fn decode_tickers(text: &str) -> Vec<MarketTicker> {
+  Client::new()
+    .decode(text)
+    .into_iter()
+    .map(|event| match event.unwrap() {
+      MarketEvent::Ticker(ticker) => ticker,
+      event => panic!("unexpected {event:?}"),
+    })
+    .collect()
+}
+
+#[test]
+fn decodes_ticker_snapshot() {
+  let tickers = decode_tickers(include_str!("fixtures/kraken/ticker_snapshot.json"));
+
+  assert_eq!(tickers.len(), 2);
+  assert_eq!(tickers[0].exchange, "kraken");
+  assert_eq!(tickers[0].symbol, "btc");
+  assert_eq!(tickers[0].ticker_name, "btc/usd");
+  assert_eq!(tickers[0].ask_price, 26566.0);
+  assert_eq!(tickers[0].bid_price, 26565.9);
+  assert_eq!(tickers[0].volume, Some(1287.4039));
+  assert_eq!(tickers[0].percent_change, Some(1.59));
+  assert_eq!(tickers[0].exchange_timestamp, None);
+  assert_eq!(tickers[1].symbol, "doge");
+}
+
+#[test]
+fn decodes_ticker_update_with_timestamp() {
+  let mut tickers = decode_tickers(include_str!("fixtures/kraken/ticker_update.json"));
+  let ticker = tickers.remove(0);
+
+  assert_eq!(ticker.symbol, "eth");
+  assert_eq!(ticker.percent_change, Some(-0.54));
+  assert_eq!(ticker.exchange_timestamp, Some(1695632672153));
+}
+
+#[test]
+fn reports_subscription_acks() {
+  let client = Client::new();
+  let ack = client.decode(include_str!("fixtures/kraken/subscribe_ack.json"));
+  let rejected = client.decode(include_str!("fixtures/kraken/subscribe_error.json"));
+
+  assert!(matches!(ack[..], [Err(ParseError::SubscriptionAck(_))]));
+  assert!(matches!(
+    rejected[..],
+    [Err(ParseError::SubscriptionRejected(_))]
+  ));
+}
+
+#[test]
+fn ignores_heartbeat_and_status() {
+  let client = Client::new();
+
+  assert!(client
+    .decode(include_str!("fixtures/kraken/heartbeat.json"))
+    .is_empty());
+  assert!(client
+    .decode(include_str!("fixtures/kraken/status.json"))
+    .is_empty());
+}
+
+#[test]
+fn rejects_unknown_channels_and_invalid_tickers() {
+  let client = Client::new();
+  let book = client.decode(include_str!("fixtures/kraken/book.json"));
+  let invalid = client.decode(include_str!("fixtures/kraken/ticker_invalid.json"));
+
+  assert!(matches!(&book[..], [Err(ParseError::UnknownStream(channel))] if channel == "book"));
+  assert!(matches!(invalid[..], [Err(ParseError::InvalidFrame(_))]));
+}
+
+#[test]
+fn subscribes_to_usd_pairs() {
+  let subscriptions = Client::new().subscriptions();
+
+  assert_eq!(subscriptions.len(), 1);
+  assert!(subscriptions[0].contains("\"channel\":\"ticker\""));
+  assert!(subscriptions[0].contains("\"BTC/USD\""));
+  assert!(subscriptions[0].contains("\"POWR/USD\""));
+}
@@ -0,0 +1,448 @@
+mod common;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use common::{serve, Requests};
+use config::Config;
+use exchange::error::NetworkError;
+use exchange::exchange::Exchange;
+use exchange::network::NetworkStatus;
+use hmac::{Hmac, Mac};
+use hodler::models::network::{AssetStatus, Network, TransferStatus};
+use hodler::Hodler;
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::HashMap;
+
+const VENUES: [&str; 6] = ["binance", "bitkub", "kraken", "coinbase", "okx", "bybit"];
+
+const ROUTES: [(&str, &str, &str); 7] = [
+  (
+    "binance",
+    "/sapi/v1/capital/config/getall",
+    include_str!("fixtures/network/binance_networks.json"),
+  ),
+  (
+    "bitkub",
+    "/api/v4/crypto/coins",
+    include_str!("fixtures/network/bitkub_coins.json"),
+  ),
+  (
+    "kraken",
+    "/0/public/Assets",
+    include_str!("fixtures/network/kraken_assets.json"),
+  ),
+  (
+    "kraken",
+    "/0/private/WithdrawMethods",
+    include_str!("fixtures/network/kraken_withdraw_methods.json"),
+  ),
+  (
+    "coinbase",
+    "/currencies",
+    include_str!("fixtures/network/coinbase_currencies.json"),
+  ),
+  (
+    "okx",
+    "/api/v5/asset/currencies",
+    include_str!("fixtures/network/okx_currencies.json"),
+  ),
+  (
+    "bybit",
+    "/v5/asset/coin/query-info",
+    include_str!("fixtures/network/bybit_coins.json"),
+  ),
+];
+
+fn get_config() -> Config {
+  Config {
+    binance_api_key: "key".to_string(),
+    binance_api_secret: "secret".to_string(),
+    bitkub_api_key: "key".to_string(),
+    bitkub_api_secret: "secret".to_string(),
+    kraken_api_key: "key".to_string(),
+    kraken_api_secret: STANDARD.encode("secret"),
+    okx_api_key: "key".to_string(),
+    okx_api_secret: "secret".to_string(),
+    okx_api_passphrase: "passphrase".to_string(),
+    bybit_api_key: "key".to_string(),
+    bybit_api_secret: "secret".to_string(),
+    ..Config::default()
+  }
+}
+
+async fn get_network_status(
+  config: &Config,
+  overrides: &[(&'static str, &'static str)],
+) -> (NetworkStatus, HashMap<String, Requests>) {
+  let mut urls = HashMap::new();
+  let mut requests = HashMap::new();
+
+  for venue in VENUES {
+    let routes = ROUTES
+      .iter()
+      .filter(|(v, _, _)| *v == venue)
+      .map(|(_, route, body)| {
+        let body = overrides
+          .iter()
+          .find(|(r, _)| r == route)
+          .map_or(*body, |(_, body)| *body);
+
+        (*route, body)
+      })
+      .collect();
+    let (url, received) = serve(routes).await;
+
+    urls.insert(venue.to_string(), url);
+    requests.insert(venue.to_string(), received);
+  }
+
+  (NetworkStatus::with_urls(config, urls), requests)
+}
+
+async fn get_statuses(exchange: Exchange) -> Vec<AssetStatus> {
+  let (network_status, _) = get_network_status(&get_config(), &[]).await;
+
+  network_status.get_statuses(&exchange).await.unwrap()
+}
+
+fn get_header<'a>(request: &'a str, name: &str) -> &'a str {
+  request
+    .lines()
+    .find_map(|line| {
+      let (key, value) = line.split_once(": ")?;
+      key.eq_ignore_ascii_case(name).then_some(value)
+    })
+    .unwrap()
+}
+
+fn get_hmac(message: &str) -> Vec<u8> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+  mac.update(message.as_bytes());
+  mac.finalize().into_bytes().to_vec()
+}
+
+fn get_networks(asset_statuses: &[AssetStatus], symbol: &str) -> Vec<(String, bool, bool)> {
+  asset_statuses
+    .iter()
+    .find(|s| s.symbol == symbol)
+    .unwrap()
+    .networks
+    .iter()
+    .map(|network| (network.name.clone(), network.deposit, network.withdraw))
+    .collect()
+}
+
+fn get_network(name: &str, deposit: bool, withdraw: bool) -> (String, bool, bool) {
+  (name.to_string(), deposit, withdraw)
+}
+
+#[tokio::test]
+async fn reads_binance_networks_with_canonical_names() {
+  let asset_statuses = get_statuses(Exchange::BinanceEx).await;
+
+  assert_eq!(
+    asset_statuses[0].networks,
+    vec![
+      Network {
+        name: "eth".to_string(),
+        deposit: true,
+        withdraw: true,
+      },
+      Network {
+        name: "bsc".to_string(),
+        deposit: true,
+        withdraw: false,
+      },
+      Network {
+        name: "arb".to_string(),
+        deposit: true,
+        withdraw: true,
+      },
+    ]
+  );
+  assert_eq!(asset_statuses[0].symbol, "eth");
+  assert_eq!(asset_statuses[1].symbol, "luna");
+  assert_eq!(asset_statuses[1].exchange, "binance");
+}
+
+#[tokio::test]
+async fn signs_binance_requests() {
+  let (network_status, requests) = get_network_status(&get_config(), &[]).await;
+  network_status
+    .get_statuses(&Exchange::BinanceEx)
+    .await
+    .unwrap();
+  let requests = requests["binance"].lock().unwrap();
+  let target = requests[0].split(' ').nth(1).unwrap();
+  let (query, signature) = target
+    .split_once('?')
+    .unwrap()
+    .1
+    .split_once("&signature=")
+    .unwrap();
+
+  assert!(query.starts_with("timestamp="));
+  assert_eq!(signature, hex::encode(get_hmac(query)));
+  assert_eq!(get_header(&requests[0], "x-mbx-apikey"), "key");
+}
+
+#[tokio::test]
+async fn signs_bitkub_requests() {
+  let (network_status, requests) = get_network_status(&get_config(), &[]).await;
+  network_status
+    .get_statuses(&Exchange::BitkubEx)
+    .await
+    .unwrap();
+  let requests = requests["bitkub"].lock().unwrap();
+  let timestamp = get_header(&requests[0], "x-btk-timestamp");
+
+  assert_eq!(get_header(&requests[0], "x-btk-apikey"), "key");
+  assert_eq!(
+    get_header(&requests[0], "x-btk-sign"),
+    hex::encode(get_hmac(&format!("{timestamp}GET/api/v4/crypto/coins")))
+  );
+}
+
+#[tokio::test]
+async fn applies_bitkub_coin_suspensions_to_every_network() {
+  let asset_statuses = get_statuses(Exchange::BitkubEx).await;
+
+  assert_eq!(
+    get_networks(&asset_statuses, "eth"),
+    vec![get_network("eth", true, true)]
+  );
+  assert_eq!(
+    get_networks(&asset_statuses, "powr"),
+    vec![get_network("eth", false, true)]
+  );
+}
+
+#[tokio::test]
+async fn gates_kraken_withdrawal_networks_by_asset_status() {
+  let asset_statuses = get_statuses(Exchange::KrakenEx).await;
+
+  assert_eq!(
+    get_networks(&asset_statuses, "btc"),
+    vec![get_network("btc", true, false)]
+  );
+  assert_eq!(
+    get_networks(&asset_statuses, "eth"),
+    vec![
+      get_network("eth", true, true),
+      get_network("arb", true, true)
+    ]
+  );
+  assert_eq!(
+    get_networks(&asset_statuses, "dot"),
+    vec![get_network("polkadot", false, false)]
+  );
+}
+
+#[tokio::test]
+async fn signs_kraken_requests() {
+  let (network_status, requests) = get_network_status(&get_config(), &[]).await;
+  network_status
+    .get_statuses(&Exchange::KrakenEx)
+    .await
+    .unwrap();
+  let requests = requests["kraken"].lock().unwrap();
+  let request = requests
+    .iter()
+    .find(|request| request.starts_with("POST /0/private/WithdrawMethods"))
+    .unwrap();
+  let body = request.split("\r\n\r\n").nth(1).unwrap();
+  let nonce = body.strip_prefix("nonce=").unwrap();
+  let mut mac = Hmac::<Sha512>::new_from_slice(b"secret").unwrap();
+  mac.update(b"/0/private/WithdrawMethods");
+  mac.update(&Sha256::digest(format!("{nonce}{body}")));
+
+  assert_eq!(get_header(request, "api-key"), "key");
+  assert_eq!(
+    get_header(request, "api-sign"),
+    STANDARD.encode(mac.finalize().into_bytes())
+  );
+}
+
+#[tokio::test]
+async fn reads_coinbase_networks_without_credentials() {
+  let (network_status, requests) = get_network_status(&Config::default(), &[]).await;
+  let asset_statuses = network_status
+    .get_statuses(&Exchange::CoinbaseEx)
+    .await
+    .unwrap();
+
+  assert_eq!(
+    get_networks(&asset_statuses, "eth"),
+    vec![
+      get_network("eth", true, true),
+      get_network("arb", false, false)
+    ]
+  );
+  assert_eq!(
+    get_networks(&asset_statuses, "sol"),
+    vec![get_network("sol", false, false)]
+  );
+  assert!(get_networks(&asset_statuses, "usd").is_empty());
+  assert!(!requests["coinbase"].lock().unwrap()[0].contains("CB-ACCESS"));
+}
+
+#[tokio::test]
+async fn groups_okx_chains_per_currency() {
+  let asset_statuses = get_statuses(Exchange::OkxEx).await;
+
+  assert_eq!(
+    get_networks(&asset_statuses, "usdt"),
+    vec![
+      get_network("eth", true, true),
+      get_network("trx", true, false)
+    ]
+  );
+  assert_eq!(
+    get_networks(&asset_statuses, "eth"),
+    vec![get_network("arb", false, true)]
+  );
+  assert_eq!(
+    get_networks(&asset_statuses, "luna"),
+    vec![get_network("terra classic", true, true)]
+  );
+}
+
+#[tokio::test]
+async fn signs_okx_and_bybit_requests() {
+  let (network_status, requests) = get_network_status(&get_config(), &[]).await;
+  network_status.get_statuses(&Exchange::OkxEx).await.unwrap();
+  network_status
+    .get_statuses(&Exchange::BybitEx)
+    .await
+    .unwrap();
+  let okx = &requests["okx"].lock().unwrap()[0];
+  let bybit = &requests["bybit"].lock().unwrap()[0];
+  let okx_timestamp = get_header(okx, "ok-access-timestamp");
+  let bybit_timestamp = get_header(bybit, "x-bapi-timestamp");
+
+  assert_eq!(get_header(okx, "ok-access-key"), "key");
+  assert_eq!(get_header(okx, "ok-access-passphrase"), "passphrase");
+  assert_eq!(
+    get_header(okx, "ok-access-sign"),
+    STANDARD.encode(get_hmac(&format!(
+      "{okx_timestamp}GET/api/v5/asset/currencies"
+    )))
+  );
+  assert_eq!(get_header(bybit, "x-bapi-api-key"), "key");
+  assert_eq!(
+    get_header(bybit, "x-bapi-sign"),
+    hex::encode(get_hmac(&format!("{bybit_timestamp}key5000")))
+  );
+}
+
+#[tokio::test]
+async fn reads_bybit_chains() {
+  let asset_statuses = get_statuses(Exchange::BybitEx).await;
+
+  assert_eq!(
+    get_networks(&asset_statuses, "eth"),
+    vec![
+      get_network("eth", true, true),
+      get_network("arb", true, false)
+    ]
+  );
+}
+
+#[tokio::test]
+async fn reports_rejected_requests() {
+  let (network_status, _) = get_network_status(
+    &get_config(),
+    &[
+      (
+        "/api/v4/crypto/coins",
+        "{\"code\":\"B1K0004\",\"message\":\"invalid signature\",\"data\":null}",
+      ),
+      (
+        "/0/private/WithdrawMethods",
+        "{\"error\":[\"EAPI:Invalid key\"]}",
+      ),
+      (
+        "/api/v5/asset/currencies",
+        "{\"code\":\"50113\",\"msg\":\"Invalid Sign\",\"data\":[]}",
+      ),
+      (
+        "/v5/asset/coin/query-info",
+        "{\"retCode\":10004,\"retMsg\":\"error sign!\",\"result\":{}}",
+      ),
+    ],
+  )
+  .await;
+
+  for (exchange, expected) in [
+    (Exchange::BitkubEx, "invalid signature"),
+    (Exchange::KrakenEx, "EAPI:Invalid key"),
+    (Exchange::OkxEx, "Invalid Sign"),
+    (Exchange::BybitEx, "error sign!"),
+  ] {
+    assert!(matches!(
+      network_status.get_statuses(&exchange).await,
+      Err(NetworkError::Rejected(message)) if message == expected
+    ));
+  }
+}
+
+#[tokio::test]
+async fn polls_enabled_venues_with_credentials() {
+  let (network_status, requests) = get_network_status(&Config::default(), &[]).await;
+  let venues = network_status
+    .get_asset_statuses()
+    .await
+    .into_iter()
+    .map(|(venue, asset_statuses)| (venue, asset_statuses.is_ok()))
+    .collect::<Vec<_>>();
+
+  assert_eq!(venues, vec![("coinbase".to_string(), true)]);
+  assert!(requests["binance"].lock().unwrap().is_empty());
+  assert!(requests["bitkub"].lock().unwrap().is_empty());
+
+  let config = Config {
+    exchanges: vec!["binance".to_string(), "okx".to_string()],
+    ..get_config()
+  };
+  let (network_status, _) = get_network_status(&config, &[]).await;
+  let venues = network_status
+    .get_asset_statuses()
+    .await
+    .into_iter()
+    .map(|(venue, _)| venue)
+    .collect::<Vec<_>>();
+
+  assert_eq!(venues, vec!["binance", "okx"]);
+}
+
+#[tokio::test]
+async fn flags_transfer_paths_between_venues() {
+  let (network_status, _) = get_network_status(&get_config(), &[]).await;
+  let hodler = Hodler::new();
+
+  for (_, asset_statuses) in network_status.get_asset_statuses().await {
+    let mut hodler = hodler.lock().unwrap();
+    asset_statuses
+      .unwrap()
+      .into_iter()
+      .for_each(|asset_status| hodler.upsert_asset_status(asset_status));
+  }
+
+  let hodler = hodler.lock().unwrap();
+  let eth = hodler.get_transfer("eth", "binance", "bitkub");
+  let powr = hodler.get_transfer("powr", "binance", "bitkub");
+  let luna = hodler.get_transfer("luna", "bitkub", "binance");
+
+  assert_eq!(eth.status, TransferStatus::Available);
+  assert_eq!(eth.networks, vec!["eth"]);
+  assert_eq!(powr.status, TransferStatus::Suspended);
+  assert_eq!(luna.status, TransferStatus::NoCommonChain);
+  assert_eq!(
+    hodler.get_transfer("eth", "okx", "kraken").networks,
+    vec!["arb"]
+  );
+  assert_eq!(
+    hodler.get_transfer("btc", "binance", "bitkub").status,
+    TransferStatus::Unknown
+  );
+}
@@ -0,0 +1,100 @@
+use config::Config;
+use exchange::client::ExchangeClient;
+use exchange::error::ParseError;
+use exchange::event::MarketEvent;
+use exchange::okx::Client;
+use serde_json::Value;
+use tokio_tungstenite::tungstenite::Message;
+
+#[test]
+fn decodes_tickers() {
+  let events = Client::new().decode(include_str!("fixtures/okx/ticker.json"));
+  let ticker = match &events[..] {
+    [Ok(MarketEvent::Ticker(ticker))] => ticker,
+    events => panic!("unexpected {events:?}"),
+  };
+
+  assert_eq!(ticker.exchange, "okx");
+  assert_eq!(ticker.symbol, "btc");
+  assert_eq!(ticker.ticker_name, "btc-usdt");
+  assert_eq!(ticker.ask_price, 26500.2);
+  assert_eq!(ticker.ask_quantity, Some(1.5));
+  assert_eq!(ticker.bid_price, 26500.1);
+  assert_eq!(ticker.bid_quantity, Some(0.8));
+  assert_eq!(ticker.volume, Some(9500.5));
+  assert_eq!(ticker.exchange_timestamp, Some(1695632672153));
+  assert!((ticker.percent_change.unwrap() - 1.9234).abs() < 0.001);
+}
+
+#[test]
+fn leaves_change_unset_without_an_open_price() {
+  let events = Client::new().decode(include_str!("fixtures/okx/ticker_unopened.json"));
+
+  match &events[..] {
+    [Ok(MarketEvent::Ticker(ticker))] => assert_eq!(ticker.percent_change, None),
+    events => panic!("unexpected {events:?}"),
+  }
+}
+
+#[test]
+fn reports_subscription_acks() {
+  let client = Client::new();
+  let ack = client.decode(include_str!("fixtures/okx/subscribe_ack.json"));
+  let rejected = client.decode(include_str!("fixtures/okx/subscribe_error.json"));
+
+  assert!(matches!(ack[..], [Err(ParseError::SubscriptionAck(_))]));
+  assert!(matches!(
+    rejected[..],
+    [Err(ParseError::SubscriptionRejected(_))]
+  ));
+}
+
+#[test]
+fn pings_with_literal_text_and_ignores_pong() {
+  let client = Client::new();
+
+  assert_eq!(client.ping(), Message::Text("ping".into()));
+  assert!(client.decode("pong").is_empty());
+}
+
+#[test]
+fn rejects_unknown_channels_and_invalid_tickers() {
+  let client = Client::new();
+  let candle = client.decode(include_str!("fixtures/okx/candle.json"));
+  let invalid = client.decode(include_str!("fixtures/okx/ticker_invalid.json"));
+
+  assert!(
+    matches!(&candle[..], [Err(ParseError::UnknownStream(channel))] if channel == "candle1m")
+  );
+  assert!(matches!(
+    invalid[..],
+    [Err(ParseError::InvalidNumber { field: "askPx", .. })]
+  ));
+  assert!(matches!(
+    client.decode("{")[..],
+    [Err(ParseError::InvalidFrame(_))]
+  ));
+}
+
+#[test]
+fn batches_subscriptions() {
+  let config = Config {
+    symbols: (1..45).map(|i| format!("s{i}")).collect(),
+    ..Config::default()
+  };
+  let batches = Client::with_config(&config)
+    .subscriptions()
+    .iter()
+    .map(|subscription| serde_json::from_str::<Value>(subscription).unwrap())
+    .collect::<Vec<_>>();
+  let sizes = batches
+    .iter()
+    .map(|batch| batch["args"].as_array().unwrap().len())
+    .collect::<Vec<_>>();
+
+  assert_eq!(sizes, vec![20, 20, 5]);
+  assert_eq!(batches[0]["op"], "subscribe");
+  assert_eq!(batches[0]["args"][0]["channel"], "tickers");
+  assert_eq!(batches[0]["args"][0]["instId"], "BTC-USDT");
+  assert_eq!(batches[2]["args"][4]["instId"], "S44-USDT");
+}
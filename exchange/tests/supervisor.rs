@@ -0,0 +1,222 @@
+use chrono::Utc;
+use exchange::client::{ExchangeClient, Shard};
+use exchange::error::ParseError;
+use exchange::event::MarketEvent;
+use exchange::exchange::Exchange;
+use exchange::supervisor::{Backoff, Disconnect, Supervisor};
+use futures_util::{SinkExt, StreamExt};
+use hodler::models::ticker::MarketTicker;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::spawn;
+use tokio_tungstenite::accept_async;
+use tokio_tungstenite::tungstenite::Message;
+
+const IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Client {
+  ticker_urls: Vec<String>,
+  ping_interval: Option<Duration>,
+}
+
+impl Client {
+  fn new(ticker_url: String) -> Self {
+    Self {
+      ticker_urls: vec![ticker_url],
+      ping_interval: None,
+    }
+  }
+}
+
+impl ExchangeClient for Client {
+  fn name(&self) -> Exchange {
+    Exchange::BitkubEx
+  }
+
+  fn get_shards(&self) -> Vec<Shard> {
+    self
+      .ticker_urls
+      .iter()
+      .map(|ticker_url| Shard {
+        ticker_url: ticker_url.clone(),
+        subscriptions: Vec::new(),
+      })
+      .collect()
+  }
+
+  fn ping_interval(&self) -> Option<Duration> {
+    self.ping_interval
+  }
+
+  fn ping(&self) -> Message {
+    Message::Text("{\"op\":\"ping\"}".into())
+  }
+
+  fn decode(&self, text: &str) -> Vec<Result<MarketEvent, ParseError>> {
+    vec![Ok(MarketEvent::Ticker(MarketTicker {
+      exchange: self.name().get_name(),
+      symbol: text.to_string(),
+      ticker_name: text.to_string(),
+      ask_price: 1.0,
+      ask_quantity: None,
+      bid_price: 1.0,
+      bid_quantity: None,
+      volume: None,
+      percent_change: None,
+      exchange_timestamp: None,
+      received_at: Utc::now().timestamp_millis(),
+    }))]
+  }
+}
+
+fn get_symbol(event: Result<MarketEvent, ParseError>) -> String {
+  match event.unwrap() {
+    MarketEvent::Ticker(ticker) => ticker.symbol,
+    event => panic!("unexpected {event:?}"),
+  }
+}
+
+fn get_backoff(max_attempts: u32) -> Backoff {
+  Backoff {
+    initial_delay: Duration::from_millis(10),
+    max_delay: Duration::from_millis(50),
+    multiplier: 2.0,
+    jitter: 0.5,
+    max_attempts: Some(max_attempts),
+  }
+}
+
+#[tokio::test]
+async fn reconnects_after_dropped_connections() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let ticker_url = format!("ws://{}", listener.local_addr().unwrap());
+
+  spawn(async move {
+    for symbol in ["btc", "eth", "ada"] {
+      let (stream, _) = listener.accept().await.unwrap();
+      let mut ws = accept_async(stream).await.unwrap();
+      ws.send(Message::Text(symbol.into())).await.unwrap();
+    }
+  });
+
+  let supervisor = Supervisor::new(
+    Box::new(Client::new(ticker_url)),
+    get_backoff(3),
+    IDLE_TIMEOUT,
+  );
+  let mut symbols = Vec::new();
+  let disconnects = supervisor
+    .run(|event| symbols.push(get_symbol(event)))
+    .await;
+
+  assert_eq!(symbols, vec!["btc", "eth", "ada"]);
+  assert!(matches!(disconnects[..], [Disconnect::ConnectFailed(_)]));
+}
+
+#[tokio::test]
+async fn merges_shards_into_one_stream() {
+  let mut ticker_urls = Vec::new();
+
+  for symbol in ["btc", "eth"] {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    ticker_urls.push(format!("ws://{}", listener.local_addr().unwrap()));
+
+    spawn(async move {
+      let (stream, _) = listener.accept().await.unwrap();
+      let mut ws = accept_async(stream).await.unwrap();
+      ws.send(Message::Text(symbol.into())).await.unwrap();
+    });
+  }
+
+  let client = Client {
+    ticker_urls,
+    ..Client::new(String::new())
+  };
+  let supervisor = Supervisor::new(Box::new(client), get_backoff(1), IDLE_TIMEOUT);
+  let mut symbols = Vec::new();
+  let disconnects = supervisor
+    .run(|event| symbols.push(get_symbol(event)))
+    .await;
+
+  symbols.sort();
+  assert_eq!(symbols, vec!["btc", "eth"]);
+  assert_eq!(disconnects.len(), 2);
+}
+
+#[tokio::test]
+async fn gives_up_when_unreachable() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let ticker_url = format!("ws://{}", listener.local_addr().unwrap());
+  drop(listener);
+
+  let supervisor = Supervisor::new(
+    Box::new(Client::new(ticker_url)),
+    get_backoff(2),
+    IDLE_TIMEOUT,
+  );
+  let disconnects = supervisor.run(|_| panic!("unexpected ticker")).await;
+
+  assert!(matches!(disconnects[..], [Disconnect::ConnectFailed(_)]));
+}
+
+#[tokio::test]
+async fn pings_and_drops_idle_feed() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let ticker_url = format!("ws://{}", listener.local_addr().unwrap());
+
+  let server = spawn(async move {
+    let (stream, _) = listener.accept().await.unwrap();
+    let mut ws = accept_async(stream).await.unwrap();
+    let mut pings = 0;
+    drop(listener);
+
+    ws.send(Message::Text("btc".into())).await.unwrap();
+
+    while let Some(Ok(message)) = ws.next().await {
+      if message == Message::Text("{\"op\":\"ping\"}".into()) {
+        pings += 1;
+      }
+    }
+
+    pings
+  });
+
+  let client = Client {
+    ping_interval: Some(Duration::from_millis(50)),
+    ..Client::new(ticker_url)
+  };
+  let supervisor = Supervisor::new(Box::new(client), get_backoff(1), Duration::from_millis(300));
+  let mut symbols = Vec::new();
+  let disconnects = supervisor
+    .run(|event| symbols.push(get_symbol(event)))
+    .await;
+
+  assert_eq!(symbols, vec!["btc"]);
+  assert!(matches!(disconnects[..], [Disconnect::ConnectFailed(_)]));
+  assert!(server.await.unwrap() >= 3);
+}
+
+#[test]
+fn backoff_grows_exponentially_up_to_max_delay() {
+  let backoff = Backoff {
+    jitter: 0.0,
+    ..get_backoff(10)
+  };
+
+  assert_eq!(backoff.get_delay(0), Duration::from_millis(10));
+  assert_eq!(backoff.get_delay(1), Duration::from_millis(20));
+  assert_eq!(backoff.get_delay(2), Duration::from_millis(40));
+  assert_eq!(backoff.get_delay(3), Duration::from_millis(50));
+  assert!(!backoff.is_exhausted(9));
+  assert!(backoff.is_exhausted(10));
+}
+
+#[test]
+fn backoff_jitter_stays_within_bounds() {
+  let backoff = get_backoff(10);
+
+  (0..100).for_each(|_| {
+    let delay = backoff.get_delay(1);
+    assert!(delay >= Duration::from_millis(10) && delay <= Duration::from_millis(30));
+  });
+}
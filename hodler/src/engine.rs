@@ -0,0 +1,159 @@
+use crate::models::currency::Cryptocurrency;
+use crate::models::signal::{Signal, SignalSide, SignalStatus, SignalThreshold};
+use config::Config;
+use std::collections::HashMap;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+#[derive(Clone)]
+enum Opportunity {
+  Pending {
+    since: i64,
+  },
+  Open {
+    buy_exchange: String,
+    sell_exchange: String,
+  },
+  Cooldown {
+    until: i64,
+  },
+}
+
+#[derive(Clone)]
+pub struct SignalEngine {
+  entry_threshold: f32,
+  exit_threshold: f32,
+  persistence_ms: i64,
+  cooldown_ms: i64,
+  opportunities: HashMap<String, Opportunity>,
+  sender: UnboundedSender<Signal>,
+}
+
+impl SignalEngine {
+  pub fn new(config: &Config) -> (Self, UnboundedReceiver<Signal>) {
+    let (sender, receiver) = unbounded_channel();
+    let mut signal_engine = Self {
+      entry_threshold: 0.0,
+      exit_threshold: 0.0,
+      persistence_ms: 0,
+      cooldown_ms: 0,
+      opportunities: HashMap::new(),
+      sender,
+    };
+    signal_engine.set_config(config);
+
+    (signal_engine, receiver)
+  }
+
+  pub fn set_config(&mut self, config: &Config) {
+    self.entry_threshold = SignalThreshold::Arbitrage.value(config);
+    self.exit_threshold = SignalThreshold::ArbitrageExit.value(config);
+    self.persistence_ms = config.signal_persistence_ms as i64;
+    self.cooldown_ms = config.signal_cooldown_ms as i64;
+  }
+
+  pub fn retain(&mut self, symbols: &[String]) {
+    self
+      .opportunities
+      .retain(|symbol, _| symbols.contains(symbol));
+  }
+
+  pub fn evaluate(&mut self, symbol: &str, exchanges: &HashMap<String, Cryptocurrency>, now: i64) {
+    let opportunity = match self.opportunities.remove(symbol) {
+      Some(Opportunity::Open {
+        buy_exchange,
+        sell_exchange,
+      }) => match (exchanges.get(&buy_exchange), exchanges.get(&sell_exchange)) {
+        (Some(buy), Some(sell)) if get_spread(buy, sell) >= self.exit_threshold => {
+          Some(Opportunity::Open {
+            buy_exchange,
+            sell_exchange,
+          })
+        }
+        (buy, sell) => {
+          self.send(SignalStatus::Close, buy, sell);
+          Some(Opportunity::Cooldown {
+            until: now + self.cooldown_ms,
+          })
+        }
+      },
+      Some(Opportunity::Cooldown { until }) if now < until => Some(Opportunity::Cooldown { until }),
+      opportunity => {
+        let since = match opportunity {
+          Some(Opportunity::Pending { since }) => since,
+          _ => now,
+        };
+
+        match get_best(exchanges) {
+          Some((buy, sell)) if get_spread(buy, sell) >= self.entry_threshold => {
+            match now - since >= self.persistence_ms {
+              true => {
+                self.send(SignalStatus::Open, Some(buy), Some(sell));
+                Some(Opportunity::Open {
+                  buy_exchange: buy.exchange.clone(),
+                  sell_exchange: sell.exchange.clone(),
+                })
+              }
+              false => Some(Opportunity::Pending { since }),
+            }
+          }
+          _ => None,
+        }
+      }
+    };
+
+    if let Some(opportunity) = opportunity {
+      self.opportunities.insert(symbol.to_string(), opportunity);
+    }
+  }
+
+  fn send(
+    &self,
+    status: SignalStatus,
+    buy: Option<&Cryptocurrency>,
+    sell: Option<&Cryptocurrency>,
+  ) {
+    if let Some(buy) = buy {
+      _ = self.sender.send(Signal {
+        status: status.clone(),
+        side: SignalSide::Buy,
+        exchange: buy.exchange.clone(),
+        symbol: buy.ticker_name.clone(),
+        symbol_key: buy.symbol.clone(),
+        original_price: buy.ask_original,
+        price: buy.ask_price,
+      });
+    }
+
+    if let Some(sell) = sell {
+      _ = self.sender.send(Signal {
+        status,
+        side: SignalSide::Sell,
+        exchange: sell.exchange.clone(),
+        symbol: sell.ticker_name.clone(),
+        symbol_key: sell.symbol.clone(),
+        original_price: sell.bid_original,
+        price: sell.bid_price,
+      });
+    }
+  }
+}
+
+fn get_best(
+  exchanges: &HashMap<String, Cryptocurrency>,
+) -> Option<(&Cryptocurrency, &Cryptocurrency)> {
+  let buy = exchanges
+    .values()
+    .min_by(|a, b| a.ask_price.total_cmp(&b.ask_price))?;
+  let sell = exchanges
+    .values()
+    .max_by(|a, b| a.bid_price.total_cmp(&b.bid_price))?;
+
+  match buy.exchange != sell.exchange {
+    true => Some((buy, sell)),
+    false => None,
+  }
+}
+
+fn get_spread(buy: &Cryptocurrency, sell: &Cryptocurrency) -> f32 {
+  sell.bid_price / buy.ask_price - 1.0
+}
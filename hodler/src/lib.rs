@@ -1,14 +1,34 @@
+pub mod engine;
 pub mod models;
 
+use self::engine::SignalEngine;
+use self::models::book::OrderBook;
 use self::models::currency::{Cryptocurrency, Currency};
+use self::models::network::{AssetStatus, Transfer, TransferStatus};
+use self::models::premium::{FxRate, PremiumIndex, SymbolPremium};
+use self::models::quote::Quote;
+use self::models::signal::Signal;
 use self::models::ticker::MarketTicker;
+use self::models::trade::{MarketTrade, TradeWindow};
+use self::models::triangle::{Market, MarketPair, Triangle};
+use config::Config;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::UnboundedReceiver;
 
 #[derive(Clone)]
 pub struct Hodler {
   pub currencies: HashMap<String, Currency>,
   pub cryptocurrencies: HashMap<String, HashMap<String, Cryptocurrency>>,
+  pub order_books: HashMap<String, HashMap<String, OrderBook>>,
+  pub trades: HashMap<String, HashMap<String, TradeWindow>>,
+  pub pairs: HashMap<String, HashMap<String, MarketPair>>,
+  pub parse_errors: HashMap<String, HashMap<String, u64>>,
+  pub asset_statuses: HashMap<String, HashMap<String, AssetStatus>>,
+  pub quotes: HashMap<String, Quote>,
+  pub fx_rate: Option<FxRate>,
+  pub signal_engine: Option<SignalEngine>,
+  pub config: Config,
 }
 
 impl Hodler {
@@ -16,11 +36,92 @@ impl Hodler {
     let hodler = Self {
       currencies: HashMap::new(),
       cryptocurrencies: HashMap::new(),
+      order_books: HashMap::new(),
+      trades: HashMap::new(),
+      pairs: HashMap::new(),
+      parse_errors: HashMap::new(),
+      asset_statuses: HashMap::new(),
+      quotes: HashMap::new(),
+      fx_rate: None,
+      signal_engine: None,
+      config: Config::default(),
     };
 
     Arc::new(Mutex::new(hodler))
   }
 
+  pub fn subscribe_signals(&mut self, config: &Config) -> UnboundedReceiver<Signal> {
+    let (signal_engine, receiver) = SignalEngine::new(config);
+    self.signal_engine = Some(signal_engine);
+
+    receiver
+  }
+
+  pub fn set_config(&mut self, config: &Config) {
+    self.config = config.clone();
+
+    if let Some(signal_engine) = self.signal_engine.as_mut() {
+      signal_engine.set_config(config);
+    }
+  }
+
+  pub fn retain(&mut self, symbols: &[String], exchanges: &[String]) {
+    let is_listed = |symbol: &String| symbols.contains(symbol) || symbol == config::CURRENCY_SYMBOL;
+
+    self
+      .currencies
+      .retain(|exchange, _| exchanges.contains(exchange));
+    self
+      .quotes
+      .retain(|exchange, _| exchanges.contains(exchange));
+    self
+      .pairs
+      .retain(|exchange, _| exchanges.contains(exchange));
+    self
+      .cryptocurrencies
+      .retain(|symbol, _| symbols.contains(symbol));
+    self
+      .order_books
+      .retain(|symbol, _| symbols.contains(symbol));
+    self.trades.retain(|symbol, _| symbols.contains(symbol));
+    self
+      .asset_statuses
+      .retain(|symbol, _| symbols.contains(symbol));
+    self
+      .cryptocurrencies
+      .values_mut()
+      .for_each(|e| e.retain(|exchange, _| exchanges.contains(exchange)));
+    self
+      .order_books
+      .values_mut()
+      .for_each(|e| e.retain(|exchange, _| exchanges.contains(exchange)));
+    self
+      .trades
+      .values_mut()
+      .for_each(|e| e.retain(|exchange, _| exchanges.contains(exchange)));
+    self
+      .asset_statuses
+      .values_mut()
+      .for_each(|e| e.retain(|exchange, _| exchanges.contains(exchange)));
+    self
+      .pairs
+      .values_mut()
+      .for_each(|pairs| pairs.retain(|_, pair| is_listed(&pair.base) && is_listed(&pair.quote)));
+
+    if let Some(signal_engine) = self.signal_engine.as_mut() {
+      signal_engine.retain(symbols);
+    }
+  }
+
+  pub fn record_parse_error(&mut self, exchange: &str, kind: &str) {
+    *self
+      .parse_errors
+      .entry(exchange.to_string())
+      .or_default()
+      .entry(kind.to_string())
+      .or_default() += 1;
+  }
+
   pub fn upsert_currency(&mut self, market_ticker: &MarketTicker) {
     self.currencies.insert(
       market_ticker.exchange.clone(),
@@ -28,23 +129,348 @@ impl Hodler {
         exchange: market_ticker.exchange.clone(),
         ask_price: market_ticker.ask_price,
         bid_price: market_ticker.bid_price,
-        timestamp: market_ticker.timestamp,
+        timestamp: market_ticker.timestamp(),
       },
     );
   }
 
+  pub fn upsert_order_book(&mut self, order_book: OrderBook) {
+    self
+      .order_books
+      .entry(order_book.symbol.clone())
+      .or_default()
+      .insert(order_book.exchange.clone(), order_book);
+  }
+
+  pub fn upsert_trade(&mut self, market_trade: MarketTrade) {
+    let exchange = market_trade.exchange.clone();
+    let symbol = market_trade.symbol.clone();
+    let timestamp = market_trade.timestamp;
+
+    self
+      .trades
+      .entry(symbol.clone())
+      .or_default()
+      .entry(exchange.clone())
+      .or_default()
+      .push(market_trade, self.config.vwap_window_ms);
+
+    let (vwap, trade_count) = self.get_vwap(&exchange, &symbol, timestamp);
+
+    if let Some(cryptocurrency) = self
+      .cryptocurrencies
+      .get_mut(&symbol)
+      .and_then(|exchanges| exchanges.get_mut(&exchange))
+    {
+      cryptocurrency.vwap = vwap;
+      cryptocurrency.trade_count = trade_count;
+    }
+  }
+
+  pub fn upsert_pair(&mut self, market_pair: MarketPair) {
+    self
+      .pairs
+      .entry(market_pair.exchange.clone())
+      .or_default()
+      .insert(market_pair.ticker_name.clone(), market_pair);
+  }
+
+  pub fn get_triangles(&self, now: i64) -> Vec<Triangle> {
+    let is_fresh = |timestamp: i64| now - timestamp <= self.config.triangle_max_quote_age_ms;
+    let mut triangles = self
+      .quotes
+      .iter()
+      .flat_map(|(exchange, quote)| {
+        let start = quote.get_name();
+        let mut market = Market::default();
+
+        if let Some(currency) = self
+          .currencies
+          .get(exchange)
+          .filter(|c| is_fresh(c.timestamp))
+        {
+          market.add(
+            config::CURRENCY_SYMBOL,
+            &start,
+            currency.ask_price,
+            currency.bid_price,
+          );
+        }
+
+        self
+          .cryptocurrencies
+          .values()
+          .filter_map(|exchanges| exchanges.get(exchange))
+          .filter(|c| is_fresh(c.received_at))
+          .for_each(|c| market.add(&c.symbol, &start, c.ask_original, c.bid_original));
+        self
+          .pairs
+          .get(exchange)
+          .into_iter()
+          .flat_map(|pairs| pairs.values())
+          .filter(|p| is_fresh(p.timestamp))
+          .for_each(|p| market.add(&p.base, &p.quote, p.ask_price, p.bid_price));
+
+        market.get_triangles(exchange, &start, self.config.get_fees(exchange).taker)
+      })
+      .collect::<Vec<Triangle>>();
+
+    triangles.sort_by(|a, b| b.net_rate.total_cmp(&a.net_rate));
+    triangles
+  }
+
+  pub fn upsert_asset_status(&mut self, asset_status: AssetStatus) {
+    self
+      .asset_statuses
+      .entry(asset_status.symbol.clone())
+      .or_default()
+      .insert(asset_status.exchange.clone(), asset_status);
+  }
+
+  pub fn get_transfer(&self, symbol: &str, from: &str, to: &str) -> Transfer {
+    if from == to {
+      return Transfer {
+        status: TransferStatus::Available,
+        networks: Vec::new(),
+      };
+    }
+
+    let asset_statuses = self.asset_statuses.get(symbol);
+
+    Transfer::new(
+      asset_statuses.and_then(|e| e.get(from)),
+      asset_statuses.and_then(|e| e.get(to)),
+    )
+  }
+
+  pub fn upsert_fx_rate(&mut self, fx_rate: FxRate) {
+    self.fx_rate = Some(fx_rate);
+  }
+
+  // Prefers a fresh endpoint rate; `source` tells which one was used.
+  pub fn get_fx_rate(&self, now: i64) -> Option<FxRate> {
+    if let Some(fx_rate) = &self.fx_rate {
+      if now - fx_rate.timestamp <= self.config.fx_max_age_ms {
+        return Some(fx_rate.clone());
+      }
+    }
+
+    let price = self.get_reference_price(config::STABLECOIN_SYMBOL, Quote::Thb)?;
+    let timestamp = self
+      .cryptocurrencies
+      .get(config::STABLECOIN_SYMBOL)?
+      .values()
+      .filter(|c| self.quotes.get(&c.exchange) == Some(&Quote::Thb))
+      .map(|c| c.timestamp)
+      .max()
+      .unwrap_or_default();
+
+    Some(FxRate {
+      source: format!("{}/thb", config::STABLECOIN_SYMBOL),
+      rate: price / self.get_usd_rate(Quote::Usdt)?,
+      timestamp,
+    })
+  }
+
+  pub fn get_premiums(&self, now: i64) -> Option<PremiumIndex> {
+    let fx_rate = self.get_fx_rate(now)?;
+    let symbols = self
+      .cryptocurrencies
+      .iter()
+      .filter_map(|(symbol, exchanges)| {
+        let (thai, global) = exchanges
+          .values()
+          .partition::<Vec<_>, _>(|c| self.quotes.get(&c.exchange) == Some(&Quote::Thb));
+        let thai_prices = thai
+          .iter()
+          .map(|c| (c.ask_original + c.bid_original) / 2.0 / fx_rate.rate)
+          .collect::<Vec<f32>>();
+        let global_prices = global
+          .iter()
+          .filter_map(|c| {
+            let (ask_rate, bid_rate) = self.get_rates(&c.exchange, Quote::Usd)?;
+            Some((c.ask_original * ask_rate + c.bid_original * bid_rate) / 2.0)
+          })
+          .collect::<Vec<f32>>();
+
+        if thai_prices.is_empty() || global_prices.is_empty() {
+          return None;
+        }
+
+        Some(SymbolPremium::new(
+          symbol,
+          thai_prices.iter().sum::<f32>() / thai_prices.len() as f32,
+          global_prices.iter().sum::<f32>() / global_prices.len() as f32,
+          thai.iter().map(|c| c.volume).sum(),
+        ))
+      })
+      .collect();
+
+    PremiumIndex::new(fx_rate, symbols)
+  }
+
+  pub fn get_vwap(&self, exchange: &str, symbol: &str, now: i64) -> (Option<f32>, usize) {
+    let since = now - self.config.vwap_window_ms;
+    let window = match self.trades.get(symbol).and_then(|e| e.get(exchange)) {
+      Some(window) => window,
+      None => return (None, 0),
+    };
+
+    (window.get_vwap(since), window.get_trade_count(since))
+  }
+
+  pub fn get_cryptocurrencies(
+    &self,
+    symbol: &str,
+    quote: Quote,
+    now: i64,
+  ) -> HashMap<String, Cryptocurrency> {
+    self
+      .cryptocurrencies
+      .get(symbol)
+      .into_iter()
+      .flat_map(|exchanges| exchanges.values())
+      .filter_map(|cryptocurrency| {
+        let (ask_rate, bid_rate) = self.get_rates(&cryptocurrency.exchange, quote)?;
+        let (vwap, trade_count) = self.get_vwap(&cryptocurrency.exchange, symbol, now);
+        let mut cryptocurrency = cryptocurrency.clone();
+        cryptocurrency.ask_price = cryptocurrency.ask_original * ask_rate;
+        cryptocurrency.bid_price = cryptocurrency.bid_original * bid_rate;
+        cryptocurrency.vwap = vwap.map(|vwap| vwap * (ask_rate + bid_rate) / 2.0);
+        cryptocurrency.trade_count = trade_count;
+
+        Some((cryptocurrency.exchange.clone(), cryptocurrency))
+      })
+      .collect()
+  }
+
+  pub fn get_order_books(&self, symbol: &str, quote: Quote) -> HashMap<String, OrderBook> {
+    self
+      .order_books
+      .get(symbol)
+      .into_iter()
+      .flat_map(|exchanges| exchanges.values())
+      .filter_map(|order_book| {
+        let (ask_rate, bid_rate) = self.get_rates(&order_book.exchange, quote)?;
+        let mut order_book = order_book.clone();
+        order_book.scale(ask_rate, bid_rate);
+
+        Some((order_book.exchange.clone(), order_book))
+      })
+      .collect()
+  }
+
+  pub fn get_rates(&self, exchange: &str, quote: Quote) -> Option<(f32, f32)> {
+    if quote == Quote::Btc {
+      let currency = self.currencies.get(exchange)?;
+      return Some((1.0 / currency.ask_price, 1.0 / currency.bid_price));
+    }
+
+    let local = *self.quotes.get(exchange)?;
+
+    if local == quote {
+      return Some((1.0, 1.0));
+    }
+
+    let rate = self.get_usd_rate(local)? / self.get_usd_rate(quote)?;
+    Some((rate, rate))
+  }
+
+  fn get_usd_rate(&self, quote: Quote) -> Option<f32> {
+    let usdt = self
+      .get_reference_price(config::STABLECOIN_SYMBOL, Quote::Usd)
+      .unwrap_or(1.0);
+
+    match quote {
+      Quote::Usd => Some(1.0),
+      Quote::Usdt => Some(usdt),
+      Quote::Thb => self
+        .get_reference_price(config::STABLECOIN_SYMBOL, Quote::Thb)
+        .map(|price| usdt / price),
+      Quote::Btc => self.get_reference_price(config::CURRENCY_SYMBOL, Quote::Usd),
+    }
+  }
+
+  fn get_reference_price(&self, symbol: &str, quote: Quote) -> Option<f32> {
+    let prices = self
+      .quotes
+      .iter()
+      .filter(|(_, local)| **local == quote)
+      .filter_map(|(exchange, _)| match symbol == config::CURRENCY_SYMBOL {
+        true => self
+          .currencies
+          .get(exchange)
+          .map(|c| (c.ask_price + c.bid_price) / 2.0),
+        false => self
+          .cryptocurrencies
+          .get(symbol)
+          .and_then(|e| e.get(exchange))
+          .map(|c| (c.ask_original + c.bid_original) / 2.0),
+      })
+      .collect::<Vec<f32>>();
+
+    match prices.is_empty() {
+      true => None,
+      false => Some(prices.iter().sum::<f32>() / prices.len() as f32),
+    }
+  }
+
+  // Buys with `notional` of `quote` on the buy venue, withdraws the asset to
+  // the sell venue, sells it and withdraws the proceeds back as `quote`.
+  // Converting between `quote` and a venue's local currency costs one more
+  // taker fee; fiat quotes return for free. Unknown withdrawal fees leave the
+  // rate unknown rather than free.
+  pub fn get_net_rate(
+    &self,
+    buy: &Cryptocurrency,
+    sell: &Cryptocurrency,
+    notional: f32,
+    quote: Quote,
+  ) -> Option<f32> {
+    let (_, buy_rate) = self.get_rates(&buy.exchange, quote)?;
+    let (sell_rate, _) = self.get_rates(&sell.exchange, quote)?;
+    let buy_fees = self.config.get_fees(&buy.exchange);
+    let sell_fees = self.config.get_fees(&sell.exchange);
+    let get_trades = |exchange: &str| match self.quotes.get(exchange) == Some(&quote) {
+      true => 1,
+      false => 2,
+    };
+    let (withdrawal, return_withdrawal) = match (buy.exchange == sell.exchange, quote) {
+      (true, _) => (0.0, 0.0),
+      (false, Quote::Usd | Quote::Thb) => (buy_fees.get_withdrawal(&buy.symbol)?, 0.0),
+      (false, _) => (
+        buy_fees.get_withdrawal(&buy.symbol)?,
+        sell_fees.get_withdrawal(&quote.get_name())?,
+      ),
+    };
+
+    let quantity = notional / buy_rate * (1.0 - buy_fees.taker).powi(get_trades(&buy.exchange))
+      / buy.ask_original
+      - withdrawal;
+    let proceeds = quantity.max(0.0)
+      * sell.bid_original
+      * (1.0 - sell_fees.taker).powi(get_trades(&sell.exchange))
+      * sell_rate
+      - return_withdrawal;
+
+    Some(proceeds.max(0.0) / notional - 1.0)
+  }
+
   pub fn upsert_cryptocurrency(&mut self, market_ticker: MarketTicker) {
     if market_ticker.is_currency_ticker() {
       return self.upsert_currency(&market_ticker);
     }
 
-    let currency = match self.currencies.get(&market_ticker.exchange) {
-      Some(currency) => currency,
-      None => return,
-    };
-
-    let ask_price = market_ticker.ask_price / currency.ask_price;
-    let bid_price = market_ticker.bid_price / currency.bid_price;
+    let ask_price = market_ticker.ask_price;
+    let bid_price = market_ticker.bid_price;
+    let timestamp = market_ticker.timestamp();
+    let (vwap, trade_count) = self.get_vwap(
+      &market_ticker.exchange,
+      &market_ticker.symbol,
+      market_ticker.received_at,
+    );
+    let symbol = market_ticker.symbol.clone();
+    let received_at = market_ticker.received_at;
 
     match self.cryptocurrencies.get_mut(&market_ticker.symbol) {
       Some(cryptocurrency) => {
@@ -56,11 +482,16 @@ impl Hodler {
             ticker_name: market_ticker.ticker_name,
             ask_original: market_ticker.ask_price,
             ask_price,
+            ask_quantity: market_ticker.ask_quantity,
             bid_original: market_ticker.bid_price,
             bid_price,
-            volume: market_ticker.volume,
-            percent_change: market_ticker.percent_change,
-            timestamp: market_ticker.timestamp,
+            bid_quantity: market_ticker.bid_quantity,
+            volume: market_ticker.volume.unwrap_or_default(),
+            percent_change: market_ticker.percent_change.unwrap_or_default(),
+            vwap,
+            trade_count,
+            timestamp,
+            received_at: market_ticker.received_at,
           },
         );
       }
@@ -75,15 +506,28 @@ impl Hodler {
               ticker_name: market_ticker.ticker_name,
               ask_original: market_ticker.ask_price,
               ask_price,
+              ask_quantity: market_ticker.ask_quantity,
               bid_original: market_ticker.bid_price,
               bid_price,
-              volume: market_ticker.volume,
-              percent_change: market_ticker.percent_change,
-              timestamp: market_ticker.timestamp,
+              bid_quantity: market_ticker.bid_quantity,
+              volume: market_ticker.volume.unwrap_or_default(),
+              percent_change: market_ticker.percent_change.unwrap_or_default(),
+              vwap,
+              trade_count,
+              timestamp,
+              received_at: market_ticker.received_at,
             },
           )]),
         );
       }
     };
+
+    if self.signal_engine.is_some() {
+      let exchanges = self.get_cryptocurrencies(&symbol, Quote::Btc, received_at);
+
+      if let Some(signal_engine) = self.signal_engine.as_mut() {
+        signal_engine.evaluate(&symbol, &exchanges, received_at);
+      }
+    }
   }
 }
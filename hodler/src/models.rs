@@ -1,3 +1,9 @@
+pub mod book;
 pub mod currency;
+pub mod network;
+pub mod premium;
+pub mod quote;
 pub mod signal;
 pub mod ticker;
+pub mod trade;
+pub mod triangle;
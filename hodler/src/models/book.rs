@@ -0,0 +1,182 @@
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Level {
+  pub price: f32,
+  pub quantity: f32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct OrderBook {
+  pub exchange: String,
+  pub symbol: String,
+  pub ticker_name: String,
+  pub asks: Vec<Level>,
+  pub bids: Vec<Level>,
+  pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Fill {
+  pub quantity: f32,
+  pub notional: f32,
+}
+
+impl OrderBook {
+  pub fn new(exchange: String, symbol: String, ticker_name: String) -> Self {
+    Self {
+      exchange,
+      symbol,
+      ticker_name,
+      asks: Vec::new(),
+      bids: Vec::new(),
+      timestamp: 0,
+    }
+  }
+
+  pub fn apply_ask(&mut self, price: f32, quantity: f32) {
+    apply_level(&mut self.asks, price, quantity, |a, b| a < b);
+  }
+
+  pub fn apply_bid(&mut self, price: f32, quantity: f32) {
+    apply_level(&mut self.bids, price, quantity, |a, b| a > b);
+  }
+
+  pub fn truncate(&self, depth: usize) -> Self {
+    Self {
+      asks: self.asks.iter().take(depth).copied().collect(),
+      bids: self.bids.iter().take(depth).copied().collect(),
+      ..self.clone()
+    }
+  }
+
+  pub fn scale(&mut self, ask_rate: f32, bid_rate: f32) {
+    self.asks.iter_mut().for_each(|l| l.price *= ask_rate);
+    self.bids.iter_mut().for_each(|l| l.price *= bid_rate);
+  }
+
+  pub fn buy(&self, notional: f32) -> Option<Fill> {
+    let mut fill = Fill::default();
+
+    for level in self.asks.iter() {
+      let remaining = notional - fill.notional;
+
+      if level.price * level.quantity >= remaining {
+        fill.quantity += remaining / level.price;
+        fill.notional = notional;
+        return Some(fill);
+      }
+
+      fill.quantity += level.quantity;
+      fill.notional += level.price * level.quantity;
+    }
+
+    None
+  }
+
+  pub fn sell(&self, quantity: f32) -> Option<Fill> {
+    let mut fill = Fill::default();
+
+    for level in self.bids.iter() {
+      let remaining = quantity - fill.quantity;
+
+      if level.quantity >= remaining {
+        fill.quantity = quantity;
+        fill.notional += remaining * level.price;
+        return Some(fill);
+      }
+
+      fill.quantity += level.quantity;
+      fill.notional += level.quantity * level.price;
+    }
+
+    None
+  }
+}
+
+fn apply_level(
+  levels: &mut Vec<Level>,
+  price: f32,
+  quantity: f32,
+  is_before: fn(f32, f32) -> bool,
+) {
+  let index = levels.partition_point(|l| is_before(l.price, price));
+
+  match levels.get_mut(index) {
+    Some(level) if level.price == price && quantity == 0.0 => {
+      levels.remove(index);
+    }
+    Some(level) if level.price == price => level.quantity = quantity,
+    _ if quantity == 0.0 => {}
+    _ => levels.insert(index, Level { price, quantity }),
+  }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DepthArbitrage {
+  pub buy_low_exchange: String,
+  pub sell_high_exchange: String,
+  pub notional: f32,
+  pub rate: Option<f32>,
+  pub max_profitable_quantity: f32,
+  pub max_profitable_notional: f32,
+  pub max_profit: f32,
+}
+
+impl DepthArbitrage {
+  pub fn new(buy: &OrderBook, sell: &OrderBook, notional: f32) -> Self {
+    let rate = buy
+      .buy(notional)
+      .and_then(|bought| sell.sell(bought.quantity))
+      .map(|sold| (sold.notional / notional - 1.0) * 100.0);
+    let (quantity, cost, proceeds) = get_max_profitable(&buy.asks, &sell.bids);
+
+    Self {
+      buy_low_exchange: buy.exchange.clone(),
+      sell_high_exchange: sell.exchange.clone(),
+      notional,
+      rate,
+      max_profitable_quantity: quantity,
+      max_profitable_notional: cost,
+      max_profit: proceeds - cost,
+    }
+  }
+}
+
+fn get_max_profitable(asks: &[Level], bids: &[Level]) -> (f32, f32, f32) {
+  let (mut quantity, mut cost, mut proceeds) = (0.0, 0.0, 0.0);
+  let mut asks = asks.iter().copied().peekable();
+  let mut bids = bids.iter().copied().peekable();
+  let (mut ask_left, mut bid_left) = (0.0, 0.0);
+
+  while let (Some(ask), Some(bid)) = (asks.peek(), bids.peek()) {
+    if ask.price >= bid.price {
+      break;
+    }
+
+    if ask_left == 0.0 {
+      ask_left = ask.quantity;
+    }
+
+    if bid_left == 0.0 {
+      bid_left = bid.quantity;
+    }
+
+    let traded = f32::min(ask_left, bid_left);
+    quantity += traded;
+    cost += traded * ask.price;
+    proceeds += traded * bid.price;
+    ask_left -= traded;
+    bid_left -= traded;
+
+    if ask_left == 0.0 {
+      asks.next();
+    }
+
+    if bid_left == 0.0 {
+      bids.next();
+    }
+  }
+
+  (quantity, cost, proceeds)
+}
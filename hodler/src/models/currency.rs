@@ -15,9 +15,14 @@ pub struct Cryptocurrency {
   pub ticker_name: String,
   pub ask_original: f32,
   pub ask_price: f32,
+  pub ask_quantity: Option<f32>,
   pub bid_original: f32,
   pub bid_price: f32,
+  pub bid_quantity: Option<f32>,
   pub volume: f32,
   pub percent_change: f32,
+  pub vwap: Option<f32>,
+  pub trade_count: usize,
   pub timestamp: i64,
+  pub received_at: i64,
 }
@@ -0,0 +1,71 @@
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Network {
+  pub name: String,
+  pub deposit: bool,
+  pub withdraw: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AssetStatus {
+  pub exchange: String,
+  pub symbol: String,
+  pub networks: Vec<Network>,
+  pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum TransferStatus {
+  Available,
+  Suspended,
+  NoCommonChain,
+  Unknown,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Transfer {
+  pub status: TransferStatus,
+  pub networks: Vec<String>,
+}
+
+impl Transfer {
+  pub fn new(from: Option<&AssetStatus>, to: Option<&AssetStatus>) -> Self {
+    let (from, to) = match (from, to) {
+      (Some(from), Some(to)) => (from, to),
+      _ => return Self::with_status(TransferStatus::Unknown),
+    };
+
+    let common = from
+      .networks
+      .iter()
+      .filter(|network| to.networks.iter().any(|n| n.name == network.name))
+      .collect::<Vec<_>>();
+    let networks = common
+      .iter()
+      .filter(|network| network.withdraw)
+      .filter(|network| {
+        to.networks
+          .iter()
+          .any(|n| n.name == network.name && n.deposit)
+      })
+      .map(|network| network.name.clone())
+      .collect::<Vec<_>>();
+
+    match (common.is_empty(), networks.is_empty()) {
+      (true, _) => Self::with_status(TransferStatus::NoCommonChain),
+      (false, true) => Self::with_status(TransferStatus::Suspended),
+      (false, false) => Self {
+        status: TransferStatus::Available,
+        networks,
+      },
+    }
+  }
+
+  fn with_status(status: TransferStatus) -> Self {
+    Self {
+      status,
+      networks: Vec::new(),
+    }
+  }
+}
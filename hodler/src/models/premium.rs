@@ -0,0 +1,68 @@
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FxRate {
+  pub source: String,
+  pub rate: f32,
+  pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SymbolPremium {
+  pub symbol: String,
+  pub thai_price: f32,
+  pub global_price: f32,
+  pub volume_usd: f32,
+  pub premium: f32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PremiumIndex {
+  pub fx_rate: FxRate,
+  pub index: f32,
+  pub average: f32,
+  pub symbols: Vec<SymbolPremium>,
+}
+
+impl SymbolPremium {
+  pub fn new(symbol: &str, thai_price: f32, global_price: f32, volume: f32) -> Self {
+    Self {
+      symbol: symbol.to_string(),
+      thai_price,
+      global_price,
+      volume_usd: volume * thai_price,
+      premium: (thai_price / global_price - 1.0) * 100.0,
+    }
+  }
+}
+
+impl PremiumIndex {
+  pub fn new(fx_rate: FxRate, mut symbols: Vec<SymbolPremium>) -> Option<Self> {
+    if symbols.is_empty() {
+      return None;
+    }
+
+    symbols.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
+    let n = symbols.len() as f32;
+    let average = symbols.iter().map(|s| s.premium).sum::<f32>() / n;
+    let volume_usd = symbols.iter().map(|s| s.volume_usd).sum::<f32>();
+    let index = match volume_usd > 0.0 {
+      true => {
+        symbols
+          .iter()
+          .map(|s| s.premium * s.volume_usd)
+          .sum::<f32>()
+          / volume_usd
+      }
+      false => average,
+    };
+
+    Some(Self {
+      fx_rate,
+      index,
+      average,
+      symbols,
+    })
+  }
+}
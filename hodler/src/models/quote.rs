@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Quote {
+  #[default]
+  Btc,
+  Usd,
+  Usdt,
+  Thb,
+}
+
+impl Quote {
+  pub fn get_name(&self) -> String {
+    match self {
+      Self::Btc => "btc",
+      Self::Usd => "usd",
+      Self::Usdt => "usdt",
+      Self::Thb => "thb",
+    }
+    .to_string()
+  }
+}
@@ -1,7 +1,9 @@
+use config::Config;
 use serde::Serialize;
 
 #[derive(Clone, Debug, Serialize)]
 pub struct Signal {
+  pub status: SignalStatus,
   pub side: SignalSide,
   pub exchange: String,
   pub symbol: String,
@@ -10,20 +12,28 @@ pub struct Signal {
   pub price: f32,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum SignalSide {
   Buy,
   Sell,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum SignalStatus {
+  Open,
+  Close,
+}
+
 pub enum SignalThreshold {
   Arbitrage,
+  ArbitrageExit,
 }
 
 impl SignalThreshold {
-  pub fn value(&self) -> f32 {
+  pub fn value(&self, config: &Config) -> f32 {
     match self {
-      Self::Arbitrage => config::SIGNAL_THRESHOLD,
+      Self::Arbitrage => config.signal_threshold,
+      Self::ArbitrageExit => config.signal_exit_threshold,
     }
   }
 }
@@ -1,17 +1,24 @@
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct MarketTicker {
   pub exchange: String,
   pub symbol: String,
   pub ticker_name: String,
   pub ask_price: f32,
+  pub ask_quantity: Option<f32>,
   pub bid_price: f32,
-  pub volume: f32,
-  pub percent_change: f32,
-  pub timestamp: i64,
+  pub bid_quantity: Option<f32>,
+  pub volume: Option<f32>,
+  pub percent_change: Option<f32>,
+  pub exchange_timestamp: Option<i64>,
+  pub received_at: i64,
 }
 
 impl MarketTicker {
   pub fn is_currency_ticker(&self) -> bool {
     self.symbol == config::CURRENCY_SYMBOL
   }
+
+  pub fn timestamp(&self) -> i64 {
+    self.exchange_timestamp.unwrap_or(self.received_at)
+  }
 }
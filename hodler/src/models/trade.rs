@@ -0,0 +1,68 @@
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug)]
+pub struct MarketTrade {
+  pub exchange: String,
+  pub symbol: String,
+  pub ticker_name: String,
+  pub price: f32,
+  pub quantity: f32,
+  pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TradeWindow {
+  trades: VecDeque<MarketTrade>,
+  notional: f64,
+  quantity: f64,
+}
+
+impl TradeWindow {
+  pub fn push(&mut self, market_trade: MarketTrade, window_ms: i64) {
+    let since = market_trade.timestamp - window_ms;
+
+    self.notional += market_trade.price as f64 * market_trade.quantity as f64;
+    self.quantity += market_trade.quantity as f64;
+    self.trades.push_back(market_trade);
+
+    while let Some(trade) = self.trades.front() {
+      if trade.timestamp > since {
+        break;
+      }
+
+      self.notional -= trade.price as f64 * trade.quantity as f64;
+      self.quantity -= trade.quantity as f64;
+      self.trades.pop_front();
+    }
+  }
+
+  // Trades up to `since` are left out without pruning, so reads age the
+  // window out even when no new trades arrive.
+  pub fn get_vwap(&self, since: i64) -> Option<f32> {
+    let (notional, quantity) = self.get_stale(since).fold(
+      (self.notional, self.quantity),
+      |(notional, quantity), trade| {
+        (
+          notional - trade.price as f64 * trade.quantity as f64,
+          quantity - trade.quantity as f64,
+        )
+      },
+    );
+
+    match quantity > 0.0 {
+      true => Some((notional / quantity) as f32),
+      false => None,
+    }
+  }
+
+  pub fn get_trade_count(&self, since: i64) -> usize {
+    self.trades.len() - self.get_stale(since).count()
+  }
+
+  fn get_stale(&self, since: i64) -> impl Iterator<Item = &MarketTrade> {
+    self
+      .trades
+      .iter()
+      .take_while(move |trade| trade.timestamp <= since)
+  }
+}
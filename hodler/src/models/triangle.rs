@@ -0,0 +1,71 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug)]
+pub struct MarketPair {
+  pub exchange: String,
+  pub base: String,
+  pub quote: String,
+  pub ticker_name: String,
+  pub ask_price: f32,
+  pub bid_price: f32,
+  pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Triangle {
+  pub exchange: String,
+  pub path: Vec<String>,
+  pub rate: f32,
+  pub net_rate: f32,
+}
+
+#[derive(Default)]
+pub struct Market {
+  rates: HashMap<String, HashMap<String, f32>>,
+}
+
+impl Market {
+  pub fn add(&mut self, base: &str, quote: &str, ask_price: f32, bid_price: f32) {
+    if base == quote || ask_price <= 0.0 || bid_price <= 0.0 {
+      return;
+    }
+
+    self
+      .rates
+      .entry(quote.to_string())
+      .or_default()
+      .insert(base.to_string(), 1.0 / ask_price);
+    self
+      .rates
+      .entry(base.to_string())
+      .or_default()
+      .insert(quote.to_string(), bid_price);
+  }
+
+  pub fn get_triangles(&self, exchange: &str, start: &str, fee: f32) -> Vec<Triangle> {
+    let mut triangles = Vec::new();
+
+    for (a, start_a) in self.rates.get(start).into_iter().flatten() {
+      for (b, a_b) in self.rates.get(a).into_iter().flatten() {
+        let b_start = match self.rates.get(b).and_then(|rates| rates.get(start)) {
+          Some(b_start) if b != start => b_start,
+          _ => continue,
+        };
+        let rate = start_a * a_b * b_start;
+        let net_rate = rate * (1.0 - fee).powi(3);
+
+        if net_rate > 1.0 {
+          triangles.push(Triangle {
+            exchange: exchange.to_string(),
+            path: vec![start.to_string(), a.clone(), b.clone(), start.to_string()],
+            rate: (rate - 1.0) * 100.0,
+            net_rate: (net_rate - 1.0) * 100.0,
+          });
+        }
+      }
+    }
+
+    triangles
+  }
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use common::{assert_close, get_ticker};
+use hodler::models::book::{DepthArbitrage, OrderBook};
+use hodler::models::quote::Quote;
+use hodler::Hodler;
+
+fn get_order_book(
+  exchange: &str,
+  symbol: &str,
+  asks: &[(f32, f32)],
+  bids: &[(f32, f32)],
+) -> OrderBook {
+  let mut order_book = OrderBook::new(exchange.to_string(), symbol.to_string(), symbol.to_string());
+  asks
+    .iter()
+    .for_each(|(price, quantity)| order_book.apply_ask(*price, *quantity));
+  bids
+    .iter()
+    .for_each(|(price, quantity)| order_book.apply_bid(*price, *quantity));
+
+  order_book
+}
+
+fn get_hodler() -> Hodler {
+  common::get_hodler(
+    &[("binance", Quote::Usdt), ("bitkub", Quote::Thb)],
+    Vec::new(),
+  )
+}
+
+#[test]
+fn stores_raw_books_without_a_currency_ticker() {
+  let mut hodler = get_hodler();
+
+  hodler.upsert_order_book(get_order_book("binance", "btc", &[(40000.0, 1.0)], &[]));
+  hodler.upsert_order_book(get_order_book("binance", "eth", &[(2000.0, 1.0)], &[]));
+
+  assert_eq!(hodler.order_books["btc"]["binance"].asks[0].price, 40000.0);
+  assert_eq!(hodler.order_books["eth"]["binance"].asks[0].price, 2000.0);
+  assert!(hodler.get_order_books("eth", Quote::Btc).is_empty());
+  assert_eq!(
+    hodler.get_order_books("eth", Quote::Usdt)["binance"].asks[0].price,
+    2000.0
+  );
+}
+
+#[test]
+fn normalizes_books_per_quote() {
+  let mut hodler = get_hodler();
+
+  hodler.upsert_cryptocurrency(get_ticker("binance", "btc", 40000.0, 0.0));
+  hodler.upsert_cryptocurrency(get_ticker("bitkub", "usdt", 35.0, 0.0));
+  hodler.upsert_order_book(get_order_book(
+    "binance",
+    "eth",
+    &[(2000.0, 1.0)],
+    &[(1990.0, 2.0)],
+  ));
+  hodler.upsert_order_book(get_order_book("bitkub", "eth", &[(70700.0, 1.0)], &[]));
+
+  let btc = hodler.get_order_books("eth", Quote::Btc);
+  let usdt = hodler.get_order_books("eth", Quote::Usdt);
+
+  assert_close(btc["binance"].asks[0].price, 0.05, 1e-3);
+  assert_close(btc["binance"].bids[0].price, 0.04975, 1e-3);
+  assert_eq!(btc["binance"].bids[0].quantity, 2.0);
+  assert!(!btc.contains_key("bitkub"));
+  assert_close(usdt["binance"].asks[0].price, 2000.0, 1e-3);
+  assert_close(usdt["bitkub"].asks[0].price, 2020.0, 1e-3);
+}
+
+#[test]
+fn sizes_depth_arbitrage_in_the_requested_quote() {
+  let mut hodler = get_hodler();
+
+  hodler.upsert_cryptocurrency(get_ticker("bitkub", "usdt", 35.0, 0.0));
+  hodler.upsert_order_book(get_order_book("binance", "eth", &[(2000.0, 1.0)], &[]));
+  hodler.upsert_order_book(get_order_book("bitkub", "eth", &[], &[(71750.0, 1.0)]));
+
+  let order_books = hodler.get_order_books("eth", Quote::Usdt);
+  let arbitrage = DepthArbitrage::new(&order_books["binance"], &order_books["bitkub"], 1000.0);
+
+  assert_close(arbitrage.rate.unwrap(), 2.5, 1e-3);
+  assert_close(arbitrage.max_profitable_quantity, 1.0, 1e-3);
+  assert_close(arbitrage.max_profit, 50.0, 1e-3);
+}
+
+#[test]
+fn buys_across_ask_levels() {
+  let order_book = get_order_book("binance", "eth", &[(100.0, 1.0), (110.0, 2.0)], &[]);
+  let fill = order_book.buy(210.0).unwrap();
+
+  assert_close(fill.quantity, 2.0, 1e-3);
+  assert_close(fill.notional, 210.0, 1e-3);
+  assert!(order_book.buy(321.0).is_none());
+}
+
+#[test]
+fn sells_across_bid_levels() {
+  let order_book = get_order_book("binance", "eth", &[], &[(100.0, 1.0), (90.0, 2.0)]);
+  let fill = order_book.sell(2.0).unwrap();
+
+  assert_close(fill.quantity, 2.0, 1e-3);
+  assert_close(fill.notional, 190.0, 1e-3);
+  assert!(order_book.sell(3.5).is_none());
+}
+
+#[test]
+fn walks_crossed_books_for_max_profit() {
+  let buy = get_order_book(
+    "binance",
+    "eth",
+    &[(100.0, 1.0), (102.0, 2.0), (106.0, 5.0)],
+    &[],
+  );
+  let sell = get_order_book(
+    "bitkub",
+    "eth",
+    &[],
+    &[(105.0, 1.5), (103.0, 1.0), (101.0, 4.0)],
+  );
+  let arbitrage = DepthArbitrage::new(&buy, &sell, 100.0);
+
+  assert_close(arbitrage.rate.unwrap(), 5.0, 1e-3);
+  assert_close(arbitrage.max_profitable_quantity, 2.5, 1e-3);
+  assert_close(arbitrage.max_profitable_notional, 253.0, 1e-3);
+  assert_close(arbitrage.max_profit, 7.5, 1e-3);
+}
+
+#[test]
+fn reports_no_rate_when_depth_runs_out() {
+  let buy = get_order_book("binance", "eth", &[(100.0, 1.0)], &[]);
+  let sell = get_order_book("bitkub", "eth", &[], &[(99.0, 1.0)]);
+  let arbitrage = DepthArbitrage::new(&buy, &sell, 150.0);
+
+  assert!(arbitrage.rate.is_none());
+  assert_eq!(arbitrage.max_profitable_quantity, 0.0);
+  assert_eq!(arbitrage.max_profit, 0.0);
+}
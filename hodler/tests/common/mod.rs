@@ -0,0 +1,42 @@
+#![allow(dead_code)]
+
+use hodler::models::quote::Quote;
+use hodler::models::ticker::MarketTicker;
+use hodler::Hodler;
+
+// Quotes around `price`, with `spread` added to the ask and taken off the bid.
+pub fn get_ticker(exchange: &str, symbol: &str, price: f32, spread: f32) -> MarketTicker {
+  MarketTicker {
+    exchange: exchange.to_string(),
+    symbol: symbol.to_string(),
+    ticker_name: symbol.to_string(),
+    ask_price: price * (1.0 + spread),
+    ask_quantity: None,
+    bid_price: price * (1.0 - spread),
+    bid_quantity: None,
+    volume: None,
+    percent_change: None,
+    exchange_timestamp: None,
+    received_at: 1,
+  }
+}
+
+pub fn get_hodler(quotes: &[(&str, Quote)], tickers: Vec<MarketTicker>) -> Hodler {
+  let mut hodler = Hodler::new().lock().unwrap().clone();
+
+  quotes.iter().for_each(|(exchange, quote)| {
+    hodler.quotes.insert(exchange.to_string(), *quote);
+  });
+  tickers
+    .into_iter()
+    .for_each(|ticker| hodler.upsert_cryptocurrency(ticker));
+
+  hodler
+}
+
+pub fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+  assert!(
+    (actual - expected).abs() < tolerance,
+    "{actual} is not close to {expected}"
+  );
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use common::{assert_close, get_ticker};
+use config::fees::Fees;
+use hodler::models::currency::Cryptocurrency;
+use hodler::models::quote::Quote;
+use hodler::models::ticker::MarketTicker;
+use hodler::Hodler;
+use std::collections::BTreeMap;
+
+fn get_fees(taker: f32, eth: f32, usdt: f32, btc: f32) -> Fees {
+  Fees {
+    maker: 0.0,
+    taker,
+    withdrawal: BTreeMap::from([
+      ("eth".to_string(), eth),
+      ("usdt".to_string(), usdt),
+      ("btc".to_string(), btc),
+    ]),
+  }
+}
+
+fn get_hodler(tickers: Vec<MarketTicker>) -> Hodler {
+  let quotes = [("binance", Quote::Usdt), ("okx", Quote::Usdt)];
+  let mut hodler = common::get_hodler(&quotes, tickers);
+
+  hodler.config.fees = BTreeMap::from([
+    ("binance".to_string(), get_fees(0.001, 0.01, 1.0, 0.0002)),
+    ("okx".to_string(), get_fees(0.002, 0.02, 2.0, 0.0004)),
+  ]);
+
+  hodler
+}
+
+fn get_eth(hodler: &Hodler, exchange: &str) -> Cryptocurrency {
+  hodler.cryptocurrencies["eth"][exchange].clone()
+}
+
+fn get_default() -> Hodler {
+  get_hodler(vec![
+    get_ticker("binance", "btc", 40000.0, 0.0),
+    get_ticker("okx", "btc", 40000.0, 0.0),
+    get_ticker("binance", "eth", 2000.0, 0.0),
+    get_ticker("okx", "eth", 2100.0, 0.0),
+  ])
+}
+
+#[test]
+fn charges_fees_and_both_withdrawals_across_venues() {
+  let hodler = get_default();
+  let net_rate = hodler
+    .get_net_rate(
+      &get_eth(&hodler, "binance"),
+      &get_eth(&hodler, "okx"),
+      10000.0,
+      Quote::Usdt,
+    )
+    .unwrap();
+  let quantity = 10000.0 * 0.999 / 2000.0 - 0.01;
+
+  assert_close(
+    net_rate,
+    (quantity * 2100.0 * 0.998 - 2.0) / 10000.0 - 1.0,
+    1e-4,
+  );
+}
+
+#[test]
+fn skips_withdrawals_on_the_same_venue() {
+  let hodler = get_default();
+  let binance = get_eth(&hodler, "binance");
+  let net_rate = hodler
+    .get_net_rate(&binance, &binance, 10000.0, Quote::Usdt)
+    .unwrap();
+
+  assert_close(net_rate, 0.999f32.powi(2) - 1.0, 1e-4);
+}
+
+#[test]
+fn converts_btc_notionals_through_each_venue() {
+  let hodler = get_default();
+  let net_rate = hodler
+    .get_net_rate(
+      &get_eth(&hodler, "binance"),
+      &get_eth(&hodler, "okx"),
+      0.25,
+      Quote::Btc,
+    )
+    .unwrap();
+  let quantity = 0.25 * 40000.0 * 0.999f32.powi(2) / 2000.0 - 0.01;
+  let proceeds = quantity * 2100.0 * 0.998f32.powi(2) / 40000.0 - 0.0004;
+
+  assert_close(net_rate, proceeds / 0.25 - 1.0, 1e-4);
+}
+
+#[test]
+fn needs_a_currency_ticker_for_btc_notionals() {
+  let hodler = get_hodler(vec![
+    get_ticker("binance", "btc", 40000.0, 0.0),
+    get_ticker("binance", "eth", 2000.0, 0.0),
+    get_ticker("okx", "eth", 2100.0, 0.0),
+  ]);
+  let binance = get_eth(&hodler, "binance");
+  let okx = get_eth(&hodler, "okx");
+
+  assert!(hodler
+    .get_net_rate(&binance, &okx, 0.25, Quote::Btc)
+    .is_none());
+  assert!(hodler
+    .get_net_rate(&binance, &okx, 10000.0, Quote::Usdt)
+    .is_some());
+}
+
+#[test]
+fn loses_the_notional_when_withdrawals_exceed_it() {
+  let hodler = get_default();
+  let net_rate = hodler
+    .get_net_rate(
+      &get_eth(&hodler, "binance"),
+      &get_eth(&hodler, "okx"),
+      10.0,
+      Quote::Usdt,
+    )
+    .unwrap();
+
+  assert_eq!(net_rate, -1.0);
+}
+
+#[test]
+fn leaves_the_rate_unknown_without_withdrawal_fees() {
+  let mut hodler = get_default();
+  let binance = get_eth(&hodler, "binance");
+  let okx = get_eth(&hodler, "okx");
+  hodler
+    .config
+    .fees
+    .get_mut("binance")
+    .unwrap()
+    .withdrawal
+    .remove("eth");
+
+  assert!(hodler
+    .get_net_rate(&binance, &okx, 10000.0, Quote::Usdt)
+    .is_none());
+  assert!(hodler
+    .get_net_rate(&okx, &binance, 10000.0, Quote::Usdt)
+    .is_some());
+  assert!(hodler
+    .get_net_rate(&binance, &binance, 10000.0, Quote::Usdt)
+    .is_some());
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use common::{assert_close, get_ticker};
+use hodler::models::premium::FxRate;
+use hodler::models::quote::Quote;
+use hodler::models::ticker::MarketTicker;
+use hodler::Hodler;
+
+fn get_traded(exchange: &str, symbol: &str, price: f32, volume: f32) -> MarketTicker {
+  MarketTicker {
+    volume: Some(volume),
+    ..get_ticker(exchange, symbol, price, 0.001)
+  }
+}
+
+fn get_hodler(tickers: Vec<MarketTicker>) -> Hodler {
+  let quotes = [
+    ("bitkub", Quote::Thb),
+    ("binance", Quote::Usdt),
+    ("kraken", Quote::Usd),
+  ];
+
+  common::get_hodler(&quotes, tickers)
+}
+
+fn get_fx_rate(rate: f32) -> FxRate {
+  FxRate {
+    source: "test".to_string(),
+    rate,
+    timestamp: 1,
+  }
+}
+
+#[test]
+fn computes_premiums_against_the_fx_rate() {
+  let mut hodler = get_hodler(vec![
+    get_traded("bitkub", "eth", 103_500.0, 10.0),
+    get_traded("kraken", "eth", 2_950.0, 100.0),
+    get_traded("binance", "eth", 2_950.0, 100.0),
+    get_traded("bitkub", "xrp", 34.0, 1_000.0),
+    get_traded("kraken", "xrp", 1.0, 1_000.0),
+  ]);
+  hodler.upsert_fx_rate(get_fx_rate(34.5));
+  let premiums = hodler.get_premiums(1).unwrap();
+
+  assert_eq!(premiums.fx_rate, get_fx_rate(34.5));
+  assert_eq!(premiums.symbols.len(), 2);
+  assert_eq!(premiums.symbols[0].symbol, "eth");
+  assert_close(premiums.symbols[0].thai_price, 3_000.0, 1e-3);
+  assert_close(premiums.symbols[0].global_price, 2_950.0, 1e-3);
+  assert_close(
+    premiums.symbols[0].premium,
+    (3_000.0 / 2_950.0 - 1.0) * 100.0,
+    1e-3,
+  );
+  assert_close(
+    premiums.symbols[1].premium,
+    (34.0 / 34.5 - 1.0) * 100.0,
+    1e-3,
+  );
+}
+
+#[test]
+fn aggregates_premiums_by_thai_volume() {
+  let mut hodler = get_hodler(vec![
+    get_traded("bitkub", "eth", 36_000.0, 3.0),
+    get_traded("kraken", "eth", 1_000.0, 100.0),
+    get_traded("bitkub", "xrp", 36.0, 1_000.0),
+    get_traded("kraken", "xrp", 1.0, 1_000.0),
+  ]);
+  hodler.upsert_fx_rate(get_fx_rate(34.2857));
+  let premiums = hodler.get_premiums(1).unwrap();
+
+  assert_close(premiums.average, 5.0, 1e-3);
+  assert_close(premiums.index, 5.0, 1e-3);
+}
+
+#[test]
+fn weights_the_index_by_traded_notional() {
+  let mut hodler = get_hodler(vec![
+    get_traded("bitkub", "eth", 35_000.0, 3.0),
+    get_traded("kraken", "eth", 1_000.0, 100.0),
+    get_traded("bitkub", "xrp", 38.5, 1_000.0),
+    get_traded("kraken", "xrp", 1.0, 1_000.0),
+  ]);
+  hodler.upsert_fx_rate(get_fx_rate(35.0));
+  let premiums = hodler.get_premiums(1).unwrap();
+
+  assert_close(premiums.average, 5.0, 1e-3);
+  assert_close(
+    premiums.index,
+    (3_000.0 * 0.0 + 1_100.0 * 10.0) / 4_100.0,
+    1e-3,
+  );
+  assert_close(premiums.symbols[0].volume_usd, 3_000.0, 1e-3);
+  assert_close(premiums.symbols[1].volume_usd, 1_100.0, 1e-3);
+}
+
+#[test]
+fn falls_back_to_the_stablecoin_rate() {
+  let hodler = get_hodler(vec![
+    get_traded("bitkub", "usdt", 35.0, 1_000.0),
+    get_traded("kraken", "usdt", 1.0, 1_000.0),
+    get_traded("bitkub", "eth", 105_000.0, 1.0),
+    get_traded("binance", "eth", 3_000.0, 1.0),
+  ]);
+  let premiums = hodler.get_premiums(1).unwrap();
+
+  assert_eq!(premiums.fx_rate.source, "usdt/thb");
+  assert_close(premiums.fx_rate.rate, 35.0, 1e-3);
+  assert_close(premiums.symbols[0].premium, 0.0, 1e-3);
+  assert_close(premiums.symbols[1].premium, 0.0, 1e-3);
+}
+
+#[test]
+fn needs_an_fx_rate_and_both_markets() {
+  let without_fx_rate = get_hodler(vec![
+    get_traded("bitkub", "eth", 105_000.0, 1.0),
+    get_traded("binance", "eth", 3_000.0, 1.0),
+  ]);
+  let mut thai_only = get_hodler(vec![get_traded("bitkub", "eth", 105_000.0, 1.0)]);
+  thai_only.upsert_fx_rate(get_fx_rate(35.0));
+
+  assert!(without_fx_rate.get_premiums(1).is_none());
+  assert!(thai_only.get_premiums(1).is_none());
+}
+
+#[test]
+fn falls_back_when_the_fx_rate_is_stale() {
+  let mut hodler = get_hodler(vec![
+    get_traded("bitkub", "usdt", 35.0, 1_000.0),
+    get_traded("kraken", "usdt", 1.0, 1_000.0),
+    get_traded("bitkub", "eth", 105_000.0, 1.0),
+    get_traded("binance", "eth", 3_000.0, 1.0),
+  ]);
+  let max_age = hodler.config.fx_max_age_ms;
+  hodler.upsert_fx_rate(get_fx_rate(34.0));
+
+  assert_eq!(
+    hodler.get_premiums(1 + max_age).unwrap().fx_rate.source,
+    "test"
+  );
+  assert_eq!(
+    hodler.get_premiums(2 + max_age).unwrap().fx_rate.source,
+    "usdt/thb"
+  );
+}
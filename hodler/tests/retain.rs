@@ -0,0 +1,74 @@
+mod common;
+
+use common::get_ticker;
+use hodler::models::quote::Quote;
+use hodler::models::triangle::MarketPair;
+use hodler::Hodler;
+
+fn get_pair(base: &str, quote: &str) -> MarketPair {
+  MarketPair {
+    exchange: "binance".to_string(),
+    base: base.to_string(),
+    quote: quote.to_string(),
+    ticker_name: format!("{base}{quote}"),
+    ask_price: 1.0,
+    bid_price: 1.0,
+    timestamp: 1,
+  }
+}
+
+fn get_hodler() -> Hodler {
+  let exchanges = ["binance", "kraken"];
+  let tickers = exchanges
+    .into_iter()
+    .flat_map(|exchange| ["btc", "eth", "xrp"].map(|symbol| get_ticker(exchange, symbol, 1.0, 0.0)))
+    .collect();
+  let mut hodler = common::get_hodler(&exchanges.map(|exchange| (exchange, Quote::Usdt)), tickers);
+
+  [("eth", "btc"), ("xrp", "btc"), ("xrp", "eth")]
+    .into_iter()
+    .for_each(|(base, quote)| hodler.upsert_pair(get_pair(base, quote)));
+  hodler
+}
+
+fn get_keys<T>(map: &std::collections::HashMap<String, T>) -> Vec<&str> {
+  let mut keys = map.keys().map(String::as_str).collect::<Vec<_>>();
+  keys.sort();
+  keys
+}
+
+#[test]
+fn drops_removed_symbols_and_exchanges() {
+  let mut hodler = get_hodler();
+
+  hodler.retain(&["eth".to_string()], &["binance".to_string()]);
+
+  assert_eq!(get_keys(&hodler.cryptocurrencies), vec!["eth"]);
+  assert_eq!(get_keys(&hodler.cryptocurrencies["eth"]), vec!["binance"]);
+  assert_eq!(get_keys(&hodler.currencies), vec!["binance"]);
+  assert_eq!(get_keys(&hodler.quotes), vec!["binance"]);
+}
+
+#[test]
+fn drops_triangle_pairs_of_removed_symbols() {
+  let mut hodler = get_hodler();
+
+  hodler.retain(
+    &["eth".to_string()],
+    &["binance".to_string(), "kraken".to_string()],
+  );
+
+  assert_eq!(get_keys(&hodler.pairs["binance"]), vec!["ethbtc"]);
+}
+
+#[test]
+fn keeps_everything_still_configured() {
+  let mut hodler = get_hodler();
+  let symbols = ["eth", "xrp"].map(String::from);
+
+  hodler.retain(&symbols, &["binance".to_string(), "kraken".to_string()]);
+
+  assert_eq!(get_keys(&hodler.cryptocurrencies), vec!["eth", "xrp"]);
+  assert_eq!(get_keys(&hodler.currencies), vec!["binance", "kraken"]);
+  assert_eq!(get_keys(&hodler.pairs["binance"]).len(), 3);
+}
@@ -0,0 +1,248 @@
+use config::Config;
+use hodler::models::signal::{Signal, SignalSide, SignalStatus};
+use hodler::models::ticker::MarketTicker;
+use hodler::Hodler;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::UnboundedReceiver;
+
+struct Feed {
+  hodler: Arc<Mutex<Hodler>>,
+  receiver: UnboundedReceiver<Signal>,
+}
+
+impl Feed {
+  fn new(exchanges: &[&str], config: Config) -> Self {
+    let hodler = Hodler::new();
+    let receiver = hodler.lock().unwrap().subscribe_signals(&config);
+    let feed = Self { hodler, receiver };
+
+    exchanges
+      .iter()
+      .for_each(|exchange| feed.upsert(0, exchange, "btc", 10.0, 10.0));
+
+    feed
+  }
+
+  fn upsert(&self, received_at: i64, exchange: &str, symbol: &str, ask_price: f32, bid_price: f32) {
+    self
+      .hodler
+      .lock()
+      .unwrap()
+      .upsert_cryptocurrency(MarketTicker {
+        exchange: exchange.to_string(),
+        symbol: symbol.to_string(),
+        ticker_name: format!("{symbol}usdt"),
+        ask_price,
+        ask_quantity: None,
+        bid_price,
+        bid_quantity: None,
+        volume: None,
+        percent_change: None,
+        exchange_timestamp: None,
+        received_at,
+      });
+  }
+
+  fn get_signals(&mut self) -> Vec<Signal> {
+    let mut signals = Vec::new();
+
+    while let Ok(signal) = self.receiver.try_recv() {
+      signals.push(signal);
+    }
+
+    signals
+  }
+
+  fn get_statuses(&mut self) -> Vec<SignalStatus> {
+    self
+      .get_signals()
+      .into_iter()
+      .map(|signal| signal.status)
+      .collect()
+  }
+}
+
+fn get_config() -> Config {
+  Config {
+    signal_threshold: 0.05,
+    signal_exit_threshold: 0.02,
+    signal_persistence_ms: 1_000,
+    signal_cooldown_ms: 10_000,
+    ..Config::default()
+  }
+}
+
+fn get_immediate_config() -> Config {
+  Config {
+    signal_persistence_ms: 0,
+    ..get_config()
+  }
+}
+
+#[test]
+fn emits_buy_and_sell_above_threshold() {
+  let mut feed = Feed::new(&["binance", "bitkub"], get_immediate_config());
+
+  feed.upsert(0, "binance", "eth", 100.0, 99.0);
+  feed.upsert(0, "bitkub", "eth", 108.0, 107.0);
+  let signals = feed.get_signals();
+
+  assert_eq!(signals.len(), 2);
+  assert_eq!(signals[0].status, SignalStatus::Open);
+  assert_eq!(signals[0].side, SignalSide::Buy);
+  assert_eq!(signals[0].exchange, "binance");
+  assert_eq!(signals[0].symbol, "ethusdt");
+  assert_eq!(signals[0].symbol_key, "eth");
+  assert_eq!(signals[0].original_price, 100.0);
+  assert_eq!(signals[0].price, 10.0);
+  assert_eq!(signals[1].status, SignalStatus::Open);
+  assert_eq!(signals[1].side, SignalSide::Sell);
+  assert_eq!(signals[1].exchange, "bitkub");
+  assert_eq!(signals[1].original_price, 107.0);
+  assert_eq!(signals[1].price, 10.7);
+}
+
+#[test]
+fn stays_silent_below_threshold() {
+  let mut feed = Feed::new(&["binance", "bitkub"], get_immediate_config());
+
+  feed.upsert(0, "binance", "eth", 100.0, 99.0);
+  feed.upsert(0, "bitkub", "eth", 104.0, 103.0);
+
+  assert!(feed.get_signals().is_empty());
+}
+
+#[test]
+fn stays_silent_on_a_single_exchange() {
+  let mut feed = Feed::new(&["binance"], get_immediate_config());
+
+  feed.upsert(0, "binance", "eth", 100.0, 110.0);
+
+  assert!(feed.get_signals().is_empty());
+}
+
+#[test]
+fn stays_silent_without_a_currency_ticker() {
+  let mut feed = Feed::new(&["binance"], get_immediate_config());
+
+  feed.upsert(0, "binance", "eth", 100.0, 99.0);
+  feed.upsert(0, "bitkub", "eth", 108.0, 107.0);
+
+  assert!(feed.get_signals().is_empty());
+}
+
+#[test]
+fn fires_once_per_opportunity() {
+  let mut feed = Feed::new(&["binance", "bitkub", "kraken"], get_immediate_config());
+
+  feed.upsert(0, "binance", "eth", 100.0, 99.0);
+  feed.upsert(0, "bitkub", "eth", 108.0, 107.0);
+  assert_eq!(feed.get_signals().len(), 2);
+
+  feed.upsert(100, "bitkub", "eth", 109.0, 108.0);
+  feed.upsert(200, "kraken", "eth", 103.0, 102.0);
+  feed.upsert(300, "binance", "eth", 100.5, 99.5);
+
+  assert!(feed.get_signals().is_empty());
+}
+
+#[test]
+fn waits_for_the_spread_to_persist() {
+  let mut feed = Feed::new(&["binance", "bitkub"], get_config());
+
+  feed.upsert(0, "binance", "eth", 100.0, 99.0);
+  feed.upsert(0, "bitkub", "eth", 108.0, 107.0);
+  feed.upsert(500, "bitkub", "eth", 108.0, 107.0);
+  assert!(feed.get_signals().is_empty());
+
+  feed.upsert(1_000, "bitkub", "eth", 108.0, 107.0);
+  assert_eq!(feed.get_statuses(), vec![SignalStatus::Open; 2]);
+}
+
+#[test]
+fn restarts_persistence_when_the_spread_collapses() {
+  let mut feed = Feed::new(&["binance", "bitkub"], get_config());
+
+  feed.upsert(0, "binance", "eth", 100.0, 99.0);
+  feed.upsert(0, "bitkub", "eth", 108.0, 107.0);
+  feed.upsert(600, "bitkub", "eth", 104.0, 103.0);
+  feed.upsert(800, "bitkub", "eth", 108.0, 107.0);
+  feed.upsert(1_200, "bitkub", "eth", 108.0, 107.0);
+  assert!(feed.get_signals().is_empty());
+
+  feed.upsert(1_800, "bitkub", "eth", 108.0, 107.0);
+  assert_eq!(feed.get_statuses(), vec![SignalStatus::Open; 2]);
+}
+
+#[test]
+fn closes_below_the_exit_threshold() {
+  let mut feed = Feed::new(&["binance", "bitkub"], get_immediate_config());
+
+  feed.upsert(0, "binance", "eth", 100.0, 99.0);
+  feed.upsert(0, "bitkub", "eth", 108.0, 107.0);
+  feed.get_signals();
+
+  feed.upsert(100, "bitkub", "eth", 104.0, 103.0);
+  assert!(feed.get_signals().is_empty());
+
+  feed.upsert(200, "bitkub", "eth", 102.0, 101.0);
+  let signals = feed.get_signals();
+
+  assert_eq!(signals.len(), 2);
+  assert_eq!(signals[0].status, SignalStatus::Close);
+  assert_eq!(signals[0].exchange, "binance");
+  assert_eq!(signals[1].status, SignalStatus::Close);
+  assert_eq!(signals[1].exchange, "bitkub");
+  assert_eq!(signals[1].original_price, 101.0);
+}
+
+#[test]
+fn closes_when_a_leg_disappears() {
+  let mut feed = Feed::new(&["binance", "bitkub"], get_immediate_config());
+
+  feed.upsert(0, "binance", "eth", 100.0, 99.0);
+  feed.upsert(0, "bitkub", "eth", 108.0, 107.0);
+  feed.get_signals();
+
+  feed.hodler.lock().unwrap().retain(
+    &["btc".to_string(), "eth".to_string()],
+    &["binance".to_string()],
+  );
+  feed.upsert(100, "binance", "eth", 100.0, 99.0);
+  let signals = feed.get_signals();
+
+  assert_eq!(signals.len(), 1);
+  assert_eq!(signals[0].status, SignalStatus::Close);
+  assert_eq!(signals[0].side, SignalSide::Buy);
+}
+
+#[test]
+fn cools_down_before_reopening() {
+  let mut feed = Feed::new(&["binance", "bitkub"], get_immediate_config());
+
+  feed.upsert(0, "binance", "eth", 100.0, 99.0);
+  feed.upsert(0, "bitkub", "eth", 108.0, 107.0);
+  feed.upsert(100, "bitkub", "eth", 101.0, 100.0);
+  assert_eq!(feed.get_signals().len(), 4);
+
+  feed.upsert(5_000, "bitkub", "eth", 108.0, 107.0);
+  assert!(feed.get_signals().is_empty());
+
+  feed.upsert(10_100, "bitkub", "eth", 108.0, 107.0);
+  assert_eq!(feed.get_statuses(), vec![SignalStatus::Open; 2]);
+}
+
+#[test]
+fn follows_threshold_changes() {
+  let mut feed = Feed::new(&["binance", "bitkub"], get_immediate_config());
+  let config = Config {
+    signal_threshold: 0.1,
+    ..get_immediate_config()
+  };
+
+  feed.hodler.lock().unwrap().set_config(&config);
+  feed.upsert(0, "binance", "eth", 100.0, 99.0);
+  feed.upsert(0, "bitkub", "eth", 108.0, 107.0);
+
+  assert!(feed.get_signals().is_empty());
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{assert_close, get_ticker};
+use hodler::models::quote::Quote;
+use hodler::models::ticker::MarketTicker;
+use hodler::models::triangle::{Market, MarketPair};
+use hodler::Hodler;
+
+const NOW: i64 = 1_000_000;
+
+fn get_pair(price: f32, timestamp: i64) -> MarketPair {
+  MarketPair {
+    exchange: "binance".to_string(),
+    base: "eth".to_string(),
+    quote: "btc".to_string(),
+    ticker_name: "ethbtc".to_string(),
+    ask_price: price,
+    bid_price: price,
+    timestamp,
+  }
+}
+
+fn get_hodler(pair_timestamp: i64) -> Hodler {
+  let tickers = [("btc", 40000.0), ("eth", 2000.0)].map(|(symbol, price)| MarketTicker {
+    received_at: NOW,
+    ..get_ticker("binance", symbol, price, 0.0)
+  });
+  let mut hodler = common::get_hodler(&[("binance", Quote::Usdt)], tickers.into());
+
+  hodler.upsert_pair(get_pair(0.049, pair_timestamp));
+  hodler
+}
+
+fn get_market() -> Market {
+  let mut market = Market::default();
+
+  market.add("btc", "usdt", 40000.0, 40000.0);
+  market.add("eth", "usdt", 2000.0, 2000.0);
+  market.add("eth", "btc", 0.049, 0.049);
+  market
+}
+
+#[test]
+fn finds_profitable_cycles_from_the_start_asset() {
+  let triangles = get_market().get_triangles("binance", "usdt", 0.001);
+
+  assert_eq!(triangles.len(), 1);
+  assert_eq!(triangles[0].exchange, "binance");
+  assert_eq!(triangles[0].path, vec!["usdt", "btc", "eth", "usdt"]);
+  assert_close(triangles[0].rate, 2.0408, 1e-3);
+  assert_close(triangles[0].net_rate, 1.7350, 1e-3);
+}
+
+#[test]
+fn drops_cycles_eaten_by_fees() {
+  assert!(get_market()
+    .get_triangles("binance", "usdt", 0.01)
+    .is_empty());
+}
+
+#[test]
+fn ignores_self_and_unpriced_pairs() {
+  let mut market = get_market();
+
+  market.add("usdt", "usdt", 1.0, 1.0);
+  market.add("xrp", "btc", 0.0, 0.00002);
+
+  assert_eq!(market.get_triangles("binance", "usdt", 0.0).len(), 1);
+}
+
+#[test]
+fn reports_hodler_triangles_from_fresh_quotes() {
+  let hodler = get_hodler(NOW);
+  let fee = hodler.config.get_fees("binance").taker;
+  let triangles = hodler.get_triangles(NOW);
+
+  assert_eq!(triangles.len(), 1);
+  assert_eq!(triangles[0].path, vec!["usdt", "btc", "eth", "usdt"]);
+  assert_close(
+    triangles[0].net_rate,
+    (2000.0 / 40000.0 / 0.049 * (1.0 - fee).powi(3) - 1.0) * 100.0,
+    1e-3,
+  );
+}
+
+#[test]
+fn skips_stale_quotes() {
+  let max_age = get_hodler(NOW).config.triangle_max_quote_age_ms;
+
+  assert!(get_hodler(NOW - max_age - 1).get_triangles(NOW).is_empty());
+  assert!(get_hodler(NOW).get_triangles(NOW + max_age + 1).is_empty());
+  assert_eq!(get_hodler(NOW - max_age).get_triangles(NOW).len(), 1);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::get_ticker;
+use hodler::models::quote::Quote;
+use hodler::models::ticker::MarketTicker;
+use hodler::models::trade::MarketTrade;
+use hodler::Hodler;
+
+fn get_trade(price: f32, timestamp: i64) -> MarketTrade {
+  MarketTrade {
+    exchange: "binance".to_string(),
+    symbol: "eth".to_string(),
+    ticker_name: "ethusdt".to_string(),
+    price,
+    quantity: 1.0,
+    timestamp,
+  }
+}
+
+fn get_hodler(window_ms: i64) -> Hodler {
+  let ticker = MarketTicker {
+    received_at: 1000,
+    ..get_ticker("binance", "eth", 2000.0, 0.0)
+  };
+  let mut hodler = common::get_hodler(&[("binance", Quote::Usdt)], vec![ticker]);
+
+  hodler.config.vwap_window_ms = window_ms;
+  hodler.upsert_trade(get_trade(1000.0, 1000));
+  hodler.upsert_trade(get_trade(2000.0, 2000));
+
+  hodler
+}
+
+#[test]
+fn ages_trades_out_of_the_window_on_read() {
+  let hodler = get_hodler(5000);
+
+  assert_eq!(hodler.get_vwap("binance", "eth", 2000), (Some(1500.0), 2));
+  assert_eq!(hodler.get_vwap("binance", "eth", 6000), (Some(2000.0), 1));
+  assert_eq!(hodler.get_vwap("binance", "eth", 7000), (None, 0));
+}
+
+#[test]
+fn prunes_the_window_on_push() {
+  let mut hodler = get_hodler(500);
+
+  assert_eq!(hodler.get_vwap("binance", "eth", 2000), (Some(2000.0), 1));
+
+  hodler.upsert_trade(get_trade(3000.0, 2400));
+  assert_eq!(hodler.get_vwap("binance", "eth", 2400), (Some(2500.0), 2));
+}
+
+#[test]
+fn serves_no_vwap_once_trades_stop() {
+  let hodler = get_hodler(5000);
+  let current = &hodler.get_cryptocurrencies("eth", Quote::Usdt, 2000)["binance"];
+  let stale = &hodler.get_cryptocurrencies("eth", Quote::Usdt, 60000)["binance"];
+
+  assert_eq!((current.vwap, current.trade_count), (Some(1500.0), 2));
+  assert_eq!((stale.vwap, stale.trade_count), (None, 0));
+}
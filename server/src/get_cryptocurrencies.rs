@@ -1,15 +1,24 @@
 use axum::{http::StatusCode, response::IntoResponse, Json};
-use hodler::{models::currency::Cryptocurrency, Hodler};
+use hodler::models::{currency::Cryptocurrency, quote::Quote};
+use hodler::Hodler;
+use serde::Deserialize;
 use std::sync::{Arc, Mutex};
 
-pub async fn handler(hodler: Arc<Mutex<Hodler>>) -> impl IntoResponse {
+#[derive(Deserialize)]
+pub struct Parameters {
+  quote: Option<Quote>,
+}
+
+pub async fn handler(query: Parameters, hodler: Arc<Mutex<Hodler>>) -> impl IntoResponse {
+  let hodler = hodler.lock().unwrap();
+  let quote = query.quote.unwrap_or_default();
+  let now = crate::get_now();
   let cryptocurrencies = hodler
-    .lock()
-    .unwrap()
     .cryptocurrencies
-    .clone()
-    .into_values()
-    .map(|exchanges| exchanges.clone().into_values().collect())
+    .keys()
+    .map(|symbol| hodler.get_cryptocurrencies(symbol, quote, now))
+    .filter(|exchanges| !exchanges.is_empty())
+    .map(|exchanges| exchanges.into_values().collect())
     .collect::<Vec<Vec<Cryptocurrency>>>();
 
   (StatusCode::OK, Json(cryptocurrencies))
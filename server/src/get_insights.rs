@@ -1,4 +1,8 @@
 use axum::{http::StatusCode, response::IntoResponse, Json};
+use config::aliases::Aliases;
+use hodler::models::book::DepthArbitrage;
+use hodler::models::network::Transfer;
+use hodler::models::quote::Quote;
 use hodler::Hodler;
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
@@ -6,26 +10,37 @@ use std::sync::{Arc, Mutex};
 #[derive(Deserialize)]
 pub struct Parameters {
   symbol: String,
+  notional: Option<f32>,
+  quote: Option<Quote>,
 }
 
 pub async fn handler(query: Parameters, hodler: Arc<Mutex<Hodler>>) -> impl IntoResponse {
-  let exchanges = match hodler
-    .lock()
-    .unwrap()
-    .cryptocurrencies
-    .clone()
-    .get(&query.symbol)
-  {
-    Some(e) => e.clone(),
-    None => {
-      return (StatusCode::NOT_FOUND, Json(None));
-    }
-  };
+  let hodler = hodler.lock().unwrap();
+  let quote = query.quote.unwrap_or_default();
+  let exchanges = hodler.get_cryptocurrencies(&query.symbol, quote, crate::get_now());
+
+  if exchanges.is_empty() {
+    return (StatusCode::NOT_FOUND, Json(None));
+  }
+
+  let depth_arbitrages = query.notional.map(|notional| {
+    let order_books = hodler.get_order_books(&query.symbol, quote);
+
+    order_books
+      .values()
+      .flat_map(|buy| {
+        order_books
+          .values()
+          .filter(|sell| sell.exchange != buy.exchange)
+          .map(|sell| DepthArbitrage::new(buy, sell, notional))
+      })
+      .collect()
+  });
 
   let mut arbitrages = Vec::<Arbitrage>::new();
   let mut premiums = Vec::<Premium>::new();
   let cryptocurrencies_with_indexes = exchanges.clone().into_values().enumerate();
-  let mut cryptocurrencies = exchanges.into_values();
+  let mut cryptocurrencies = exchanges.clone().into_values();
   let cryptocurrency = cryptocurrencies.next().unwrap();
   let symbol = cryptocurrency.symbol;
   let mut sum_volume = cryptocurrency.volume;
@@ -40,36 +55,49 @@ pub async fn handler(query: Parameters, hodler: Arc<Mutex<Hodler>>) -> impl Into
   let mut best_bid_price = cryptocurrency.bid_price;
   let mut best_bid_ticker_name = cryptocurrency.ticker_name;
   let mut best_arbitrage = 0.0;
+  let mut best_net_arbitrage: Option<f32> = None;
   let mut best_ask_premium = 0.0;
   let mut best_bid_premium = 0.0;
 
   cryptocurrencies.for_each(|c| {
-    sum_volume += c.volume.clone();
-    sum_percent_change += c.percent_change.clone();
-    sum_ask_price += c.ask_price.clone();
-    sum_bid_price += c.bid_price.clone();
+    sum_volume += c.volume;
+    sum_percent_change += c.percent_change;
+    sum_ask_price += c.ask_price;
+    sum_bid_price += c.bid_price;
     n += 1.0;
 
-    if c.ask_price.clone() < best_ask_price {
+    if c.ask_price < best_ask_price {
       best_ask_exchange = c.exchange.clone();
-      best_ask_price = c.ask_price.clone();
+      best_ask_price = c.ask_price;
       best_ask_ticker_name = c.ticker_name.clone();
     }
 
-    if c.bid_price.clone() > best_bid_price {
+    if c.bid_price > best_bid_price {
       best_bid_exchange = c.exchange;
       best_bid_price = c.bid_price;
       best_bid_ticker_name = c.ticker_name;
     }
   });
 
+  let best_bid = exchanges[&best_bid_exchange].clone();
+  let (net_notional, net_quote) = match query.notional {
+    Some(notional) => (notional, quote),
+    None => (hodler.config.net_rate_notional, Quote::Btc),
+  };
+
   cryptocurrencies_with_indexes.for_each(|(i, c)| {
+    let net_rate = hodler
+      .get_net_rate(&c, &best_bid, net_notional, net_quote)
+      .map(|rate| rate * 100.0);
+    let transfer = hodler.get_transfer(&symbol, &c.exchange, &best_bid_exchange);
     let arbitrage = Arbitrage {
       buy_low_exchange: c.exchange.clone(),
-      buy_low_price: c.ask_price.clone(),
+      buy_low_price: c.ask_price,
       sell_high_exchange: best_bid_exchange.clone(),
-      sell_high_price: best_bid_price.clone(),
+      sell_high_price: best_bid_price,
       rate: (best_bid_price / c.ask_price - 1.0) * 100.0,
+      net_rate,
+      transfer,
     };
 
     let premium = Premium {
@@ -80,6 +108,10 @@ pub async fn handler(query: Parameters, hodler: Arc<Mutex<Hodler>>) -> impl Into
       bid_price: c.bid_price,
     };
 
+    if net_rate > best_net_arbitrage {
+      best_net_arbitrage = net_rate;
+    }
+
     if i == 0 {
       best_arbitrage = arbitrage.rate;
       best_ask_premium = premium.ask_premium;
@@ -118,11 +150,12 @@ pub async fn handler(query: Parameters, hodler: Arc<Mutex<Hodler>>) -> impl Into
     volume: sum_volume * sum_ask_price / n,
     percent_change: sum_percent_change / n,
     best_arbitrage,
+    best_net_arbitrage,
     best_ask_premium,
     best_bid_premium,
     icon: format!(
       "https://cdn.bitkubnow.com/coins/icon/{}.png",
-      symbol.replace("powr", "pow").to_uppercase()
+      Aliases::get().get_ticker("bitkub", &symbol).to_uppercase()
     ),
   };
 
@@ -130,6 +163,7 @@ pub async fn handler(query: Parameters, hodler: Arc<Mutex<Hodler>>) -> impl Into
     StatusCode::OK,
     Json(Some(Insight {
       arbitrages,
+      depth_arbitrages,
       premiums,
       summary,
     })),
@@ -139,6 +173,8 @@ pub async fn handler(query: Parameters, hodler: Arc<Mutex<Hodler>>) -> impl Into
 #[derive(Serialize)]
 pub struct Insight {
   pub arbitrages: Vec<Arbitrage>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub depth_arbitrages: Option<Vec<DepthArbitrage>>,
   pub premiums: Vec<Premium>,
   pub summary: Summary,
 }
@@ -150,6 +186,8 @@ pub struct Arbitrage {
   pub sell_high_exchange: String,
   pub sell_high_price: f32,
   pub rate: f32,
+  pub net_rate: Option<f32>,
+  pub transfer: Transfer,
 }
 
 #[derive(Serialize)]
@@ -175,6 +213,7 @@ pub struct Summary {
   pub best_bid_price: f32,
   pub best_bid_ticker_name: String,
   pub best_arbitrage: f32,
+  pub best_net_arbitrage: Option<f32>,
   pub best_ask_premium: f32,
   pub best_bid_premium: f32,
   pub icon: String,
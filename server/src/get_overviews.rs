@@ -1,15 +1,24 @@
 use axum::{http::StatusCode, response::IntoResponse, Json};
+use config::aliases::Aliases;
+use hodler::models::quote::Quote;
 use hodler::Hodler;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 
-pub async fn handler(hodler: Arc<Mutex<Hodler>>) -> impl IntoResponse {
+#[derive(Deserialize)]
+pub struct Parameters {
+  quote: Option<Quote>,
+}
+
+pub async fn handler(query: Parameters, hodler: Arc<Mutex<Hodler>>) -> impl IntoResponse {
+  let hodler = hodler.lock().unwrap();
+  let quote = query.quote.unwrap_or_default();
+  let now = crate::get_now();
   let mut overviews = hodler
-    .lock()
-    .unwrap()
     .cryptocurrencies
-    .clone()
-    .into_values()
+    .keys()
+    .map(|symbol| hodler.get_cryptocurrencies(symbol, quote, now))
+    .filter(|exchanges| !exchanges.is_empty())
     .map(|exchanges| {
       let cryptocurrencies_with_indexes = exchanges.clone().into_values().enumerate();
       let mut cryptocurrencies = exchanges.clone().into_values();
@@ -27,31 +36,41 @@ pub async fn handler(hodler: Arc<Mutex<Hodler>>) -> impl IntoResponse {
       let mut best_bid_price = cryptocurrency.ask_price;
       let mut best_bid_ticker_name = cryptocurrency.ticker_name;
       let mut best_arbitrage = 0.0;
+      let mut best_net_arbitrage: Option<f32> = None;
       let mut best_ask_premium = 0.0;
       let mut best_bid_premium = 0.0;
 
       cryptocurrencies.for_each(|c| {
-        sum_volume += c.volume.clone();
-        sum_percent_change += c.percent_change.clone();
-        sum_ask_price += c.ask_price.clone();
-        sum_bid_price += c.bid_price.clone();
+        sum_volume += c.volume;
+        sum_percent_change += c.percent_change;
+        sum_ask_price += c.ask_price;
+        sum_bid_price += c.bid_price;
         n += 1.0;
 
-        if c.ask_price.clone() < best_ask_price {
+        if c.ask_price < best_ask_price {
           best_ask_exchange = c.exchange.clone();
-          best_ask_price = c.ask_price.clone();
+          best_ask_price = c.ask_price;
           best_ask_ticker_name = c.ticker_name.clone();
         }
 
-        if c.bid_price.clone() > best_bid_price {
+        if c.bid_price > best_bid_price {
           best_bid_exchange = c.exchange.clone();
-          best_bid_price = c.bid_price.clone();
+          best_bid_price = c.bid_price;
           best_bid_ticker_name = c.ticker_name.clone();
         }
       });
 
+      let best_bid = &exchanges[&best_bid_exchange];
+
       cryptocurrencies_with_indexes.for_each(|(i, c)| {
         let arbitrage_rate = best_bid_price / c.ask_price - 1.0;
+        let net_rate =
+          hodler.get_net_rate(&c, best_bid, hodler.config.net_rate_notional, Quote::Btc);
+
+        if net_rate > best_net_arbitrage {
+          best_net_arbitrage = net_rate;
+        }
+
         let ask_premium = c.ask_price / best_ask_price - 1.0;
         let bid_premium = c.bid_price / best_bid_price - 1.0;
 
@@ -86,13 +105,14 @@ pub async fn handler(hodler: Arc<Mutex<Hodler>>) -> impl IntoResponse {
         best_bid_price,
         best_bid_ticker_name,
         best_arbitrage: best_arbitrage * 100.0,
+        best_net_arbitrage: best_net_arbitrage.map(|rate| rate * 100.0),
         best_ask_premium: best_ask_premium * 100.0,
         best_bid_premium: best_bid_premium * 100.0,
         volume: sum_volume * sum_ask_price / n,
         percent_change: sum_percent_change / n,
         icon: format!(
           "https://cdn.bitkubnow.com/coins/icon/{}.png",
-          symbol.replace("powr", "pow").to_uppercase()
+          Aliases::get().get_ticker("bitkub", &symbol).to_uppercase()
         ),
       }
     })
@@ -118,6 +138,7 @@ pub struct Overview {
   pub best_bid_price: f32,
   pub best_bid_ticker_name: String,
   pub best_arbitrage: f32,
+  pub best_net_arbitrage: Option<f32>,
   pub best_ask_premium: f32,
   pub best_bid_premium: f32,
   pub icon: String,
@@ -0,0 +1,9 @@
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use hodler::Hodler;
+use std::sync::{Arc, Mutex};
+
+pub async fn handler(hodler: Arc<Mutex<Hodler>>) -> impl IntoResponse {
+  let parse_errors = hodler.lock().unwrap().parse_errors.clone();
+
+  (StatusCode::OK, Json(parse_errors))
+}
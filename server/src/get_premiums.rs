@@ -0,0 +1,10 @@
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use hodler::Hodler;
+use std::sync::{Arc, Mutex};
+
+pub async fn handler(hodler: Arc<Mutex<Hodler>>) -> impl IntoResponse {
+  match hodler.lock().unwrap().get_premiums(crate::get_now()) {
+    Some(premiums) => (StatusCode::OK, Json(Some(premiums))),
+    None => (StatusCode::NOT_FOUND, Json(None)),
+  }
+}
@@ -0,0 +1,9 @@
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use hodler::Hodler;
+use std::sync::{Arc, Mutex};
+
+pub async fn handler(hodler: Arc<Mutex<Hodler>>) -> impl IntoResponse {
+  let triangles = hodler.lock().unwrap().get_triangles(crate::get_now());
+
+  (StatusCode::OK, Json(triangles))
+}
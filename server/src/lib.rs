@@ -3,23 +3,38 @@ mod get_cryptocurrencies;
 mod get_currencies;
 mod get_insights;
 mod get_overviews;
+mod get_parse_errors;
+mod get_premiums;
+mod get_triangles;
 mod health_check;
 
 use axum::{extract::Query, middleware, routing::get, Router, Server};
-use config::PORT;
+use config::Config;
 use get_insights::Parameters;
 use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 type Hodler = Arc<Mutex<hodler::Hodler>>;
 
 pub struct HodlerServer {}
 
 impl HodlerServer {
-  pub async fn new(h: Hodler) {
+  pub async fn serve(h: Hodler, config: &Config) {
     let health_check = get(health_check::handler);
-    let get_cryptocurrencies = |h: Hodler| get(move || get_cryptocurrencies::handler(h));
+    let get_cryptocurrencies = |h: Hodler| {
+      get(
+        move |Query(query): Query<get_cryptocurrencies::Parameters>| {
+          get_cryptocurrencies::handler(query, h)
+        },
+      )
+    };
     let get_currencies = |h: Hodler| get(move || get_currencies::handler(h));
-    let get_overviews = |h: Hodler| get(move || get_overviews::handler(h));
+    let get_overviews = |h: Hodler| {
+      get(move |Query(query): Query<get_overviews::Parameters>| get_overviews::handler(query, h))
+    };
+    let get_parse_errors = |h: Hodler| get(move || get_parse_errors::handler(h));
+    let get_premiums = |h: Hodler| get(move || get_premiums::handler(h));
+    let get_triangles = |h: Hodler| get(move || get_triangles::handler(h));
     let get_insights =
       |h: Hodler| get(move |Query(query): Query<Parameters>| get_insights::handler(query, h));
 
@@ -29,11 +44,20 @@ impl HodlerServer {
       .route("/currencies", get_currencies(h.clone()))
       .route("/overviews", get_overviews(h.clone()))
       .route("/insights", get_insights(h.clone()))
+      .route("/parse-errors", get_parse_errors(h.clone()))
+      .route("/premiums", get_premiums(h.clone()))
+      .route("/triangles", get_triangles(h.clone()))
       .route_layer(middleware::from_fn(cors::handler));
 
-    Server::bind(&format!("0.0.0.0:{PORT}").parse().unwrap())
+    Server::bind(&format!("0.0.0.0:{}", config.port).parse().unwrap())
       .serve(router.into_make_service())
       .await
       .unwrap();
   }
 }
+
+fn get_now() -> i64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_or(0, |elapsed| elapsed.as_millis() as i64)
+}
@@ -1,130 +1,263 @@
+use config::Config;
 use env_logger::{Builder, Env};
-use exchange::{binance, bitkub};
-use futures_util::StreamExt;
-use hodler::{models::ticker::MarketTicker, Hodler};
-use log::{debug, error};
-use serde_json::from_str;
+use exchange::client::{ExchangeClient, Shard};
+use exchange::discovery::Discovery;
+use exchange::event::MarketEvent;
+use exchange::exchange::Exchange;
+use exchange::fx::FxSource;
+use exchange::network::NetworkStatus;
+use exchange::supervisor::{Backoff, Supervisor};
+use hodler::models::signal::Signal;
+use hodler::Hodler;
+use log::{error, info, warn};
 use server::HodlerServer;
+use std::collections::HashMap;
+use std::future::pending;
+use std::process::exit;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(unix)]
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::watch::{channel, Receiver, Sender};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
 use tokio::{join, spawn};
-use tokio_tungstenite::tungstenite::protocol::Message;
 
 #[tokio::main]
 async fn main() {
-  Builder::from_env(Env::default().default_filter_or(config::DEFAULT_LOGGING_LEVEL)).init();
+  let config = match Config::load() {
+    Ok(config) => config,
+    Err(err) => {
+      eprintln!("{err}");
+      exit(1);
+    }
+  };
+
+  Builder::from_env(Env::default().default_filter_or(&config.logging_level)).init();
+  let config = discover(config).await;
   let hodler: Arc<Mutex<Hodler>> = Hodler::new();
-  let hodler_server = HodlerServer::new(hodler.clone());
-
-  let binance_ws_handler = |hodler: Arc<Mutex<Hodler>>| async {
-    match spawn(async move {
-      loop {
-        let binance = binance::Client::new();
-        binance
-          .connect_ws()
-          .await
-          .for_each(|message| async {
-            let message = &message.unwrap_or(Message::Close(None));
-            let text = match message {
-              Message::Text(text) => text,
-              _ => return debug!("{message:?}"),
-            };
-
-            match from_str::<binance::ticker::Ticker>(text) {
-              Ok(ticker) => {
-                hodler.lock().unwrap().upsert_cryptocurrency(MarketTicker {
-                  exchange: binance.name.get_name(),
-                  ticker_name: ticker.ticker_name.clone(),
-                  symbol: binance.name.get_key(ticker.ticker_name),
-                  ask_price: ticker.ask_price,
-                  bid_price: ticker.bid_price,
-                  volume: ticker.volume,
-                  percent_change: ticker.change,
-                  timestamp: ticker.timestamp,
-                });
-              }
-              Err(error) => error!(target: &binance.name.get_name(), "{error:?}: {message:?}"),
-            };
-          })
-          .await;
-      }
-    })
-    .await
-    {
-      Ok(out) => out,
-      Err(err) => error!("{err:?}"),
+  hodler.lock().unwrap().set_config(&config);
+  let signals = hodler.lock().unwrap().subscribe_signals(&config);
+  let hodler_server = HodlerServer::serve(hodler.clone(), &config);
+  let (sender, receiver) = channel(config.clone());
+
+  join!(
+    run_feeds(hodler.clone(), receiver.clone()),
+    poll_network_status(hodler.clone(), receiver.clone()),
+    poll_fx(hodler.clone(), receiver),
+    reload(hodler.clone(), sender),
+    log_signals(signals),
+    hodler_server
+  );
+}
+
+async fn discover(mut config: Config) -> Config {
+  if config.symbol_discovery {
+    let discovery = Discovery::new();
+
+    match discovery.get_symbols().await {
+      Ok(symbols) => config.symbols = symbols,
+      Err(err) => error!("symbol discovery failed, using configured symbols: {err}"),
+    }
+
+    config.listings = discovery
+      .get_listings(&Exchange::get_enabled(&config))
+      .await;
+  }
+
+  info!("subscribing to {} symbols", config.symbols.len());
+  config
+}
+
+#[cfg(unix)]
+async fn reload(hodler: Arc<Mutex<Hodler>>, sender: Sender<Config>) {
+  let mut hangups = match signal(SignalKind::hangup()) {
+    Ok(hangups) => hangups,
+    Err(err) => {
+      error!("reload on SIGHUP unavailable: {err}");
+      return pending().await;
     }
   };
 
-  let bitkub_ws_handler = |hodler: Arc<Mutex<Hodler>>| async {
-    match spawn(async move {
-      loop {
-        let bitkub = bitkub::Client::new();
-        bitkub
-          .connect_ws()
-          .await
-          .for_each(|message| async {
-            let message = &message.unwrap_or(Message::Close(None));
-            let text = match message {
-              Message::Text(text) => text,
-              _ => return debug!("{message:?}"),
-            };
-
-            text
-              .split("\n")
-              .for_each(|s| match from_str::<bitkub::ticker::Ticker>(s) {
-                Ok(ticker) => {
-                  hodler.lock().unwrap().upsert_cryptocurrency(MarketTicker {
-                    exchange: bitkub.name.get_name(),
-                    ticker_name: ticker.ticker_name.clone(),
-                    symbol: bitkub.name.get_key(ticker.ticker_name),
-                    ask_price: ticker.ask_price,
-                    bid_price: ticker.bid_price,
-                    volume: ticker.volume,
-                    percent_change: ticker.change,
-                    timestamp: ticker.timestamp,
-                  });
-                }
-                Err(err) => error!(target: &bitkub.name.get_name(), "{err:?}: {message:?}"),
-              })
-          })
-          .await;
+  while hangups.recv().await.is_some() {
+    let config = match Config::load() {
+      Ok(config) => discover(config).await,
+      Err(err) => {
+        error!("reload failed, keeping the current config: {err}");
+        continue;
       }
-    })
-    .await
-    {
-      Ok(out) => out,
-      Err(err) => error!("{err:?}"),
+    };
+    let changes = sender.borrow().get_changes(&config);
+
+    if changes.is_empty() {
+      info!("reload: no changes");
+      continue;
     }
-  };
 
-  // let ftx = ftx::Client::new();
-  // let ftx_ws_handler = ftx.connect_ws().await.for_each(|message| async {
-  //   let message = &message.unwrap_or(Message::Close(None));
-  //   let is_ticker = message.is_text() && message.to_string().contains("type\":\"update");
-  //   let text = if is_ticker { message } else { return };
-
-  //   match from_str::<ftx::ticker::Ticker>(&text.to_string()) {
-  //     Ok(ticker) => {
-  //       hodler
-  //         .lock()
-  //         .unwrap()
-  //         .update_market(MarketTicker {
-  //           exchange: ftx.name.get_name(),
-  //           symbol: ticker.symbol.clone(),
-  //           symbol_key: ftx.name.get_key(ticker.symbol),
-  //           ask_price: ticker.ask_price,
-  //           bid_price: ticker.bid_price,
-  //         })
-  //         .await;
-  //     }
-  //     Err(error) => error!(target: &ftx.name.get_name(), "{error:?}: {message:?}"),
-  //   }
-  // });
+    changes.iter().for_each(|change| info!("reload: {change}"));
+    hodler.lock().unwrap().set_config(&config);
+    sender.send_replace(config);
+  }
+}
 
-  join!(
-    binance_ws_handler(hodler.clone()),
-    bitkub_ws_handler(hodler.clone()),
-    // ftx_ws_handler,
-    hodler_server,
-  );
+// Holds the sender so feeds keep running on the config they started with.
+#[cfg(not(unix))]
+async fn reload(_hodler: Arc<Mutex<Hodler>>, _sender: Sender<Config>) {
+  pending().await
+}
+
+async fn log_signals(mut signals: UnboundedReceiver<Signal>) {
+  while let Some(signal) = signals.recv().await {
+    info!(
+      target: "signal",
+      "{:?} {:?} {} on {} at {} ({})",
+      signal.status,
+      signal.side, signal.symbol_key, signal.exchange, signal.price, signal.original_price
+    );
+  }
+}
+
+async fn poll_network_status(hodler: Arc<Mutex<Hodler>>, receiver: Receiver<Config>) {
+  loop {
+    let config = receiver.borrow().clone();
+
+    if config.network_status {
+      for (venue, asset_statuses) in NetworkStatus::new(&config).get_asset_statuses().await {
+        match asset_statuses {
+          Ok(asset_statuses) => {
+            let symbols = receiver.borrow().symbols.clone();
+            let mut hodler = hodler.lock().unwrap();
+            asset_statuses
+              .into_iter()
+              .filter(|asset_status| symbols.contains(&asset_status.symbol))
+              .for_each(|asset_status| hodler.upsert_asset_status(asset_status));
+          }
+          Err(err) => warn!(target: &venue, "network status: {err}"),
+        }
+      }
+    }
+
+    sleep(Duration::from_millis(
+      config.network_status_poll_interval_ms,
+    ))
+    .await;
+  }
+}
+
+async fn poll_fx(hodler: Arc<Mutex<Hodler>>, receiver: Receiver<Config>) {
+  loop {
+    let config = receiver.borrow().clone();
+
+    if config.fx {
+      match FxSource::new(&config).get_rate().await {
+        Ok(fx_rate) => hodler.lock().unwrap().upsert_fx_rate(fx_rate),
+        Err(err) => warn!("fx: {err}"),
+      }
+    }
+
+    sleep(Duration::from_millis(config.fx_poll_interval_ms)).await;
+  }
+}
+
+async fn run_feeds(hodler: Arc<Mutex<Hodler>>, mut receiver: Receiver<Config>) {
+  let mut feeds = HashMap::<String, (Vec<Shard>, JoinHandle<()>)>::new();
+  let mut running = receiver.borrow().clone();
+
+  loop {
+    let config = receiver.borrow_and_update().clone();
+    let backoff = Backoff::from(&config);
+    let clients = exchange::clients(&config);
+    let names = clients
+      .iter()
+      .map(|client| client.name().get_name())
+      .collect::<Vec<_>>();
+
+    let mut stopped = Vec::new();
+    let mut starting = Vec::new();
+
+    for name in feeds.keys().cloned().collect::<Vec<_>>() {
+      if !names.contains(&name) {
+        info!("stopping {name} feed");
+        stopped.extend(feeds.remove(&name).map(|(_, feed)| feed));
+      }
+    }
+
+    for client in clients {
+      let name = client.name().get_name();
+      let shards = client.get_shards();
+
+      if let Some((running_shards, feed)) = feeds.remove(&name) {
+        let changes = running.get_feed_changes(&config, &name);
+
+        if running_shards == shards && changes.is_empty() {
+          feeds.insert(name, (running_shards, feed));
+          continue;
+        }
+
+        info!("resubscribing {name} feed");
+        stopped.push(feed);
+      }
+
+      starting.push((client, shards));
+    }
+
+    // Prune only once the stopped feeds can no longer upsert what is removed.
+    for feed in stopped {
+      feed.abort();
+      let _ = feed.await;
+    }
+
+    hodler
+      .lock()
+      .unwrap()
+      .retain(&config.symbols, &config.exchanges);
+
+    for (client, shards) in starting {
+      let name = client.name().get_name();
+
+      hodler
+        .lock()
+        .unwrap()
+        .quotes
+        .insert(name.clone(), client.name().get_quote());
+      let feed = spawn(run_feed(
+        client,
+        hodler.clone(),
+        backoff.clone(),
+        Duration::from_millis(config.feed_idle_timeout_ms),
+      ));
+      feeds.insert(name, (shards, feed));
+    }
+
+    running = config;
+
+    if receiver.changed().await.is_err() {
+      return;
+    }
+  }
+}
+
+async fn run_feed(
+  client: Box<dyn ExchangeClient>,
+  hodler: Arc<Mutex<Hodler>>,
+  backoff: Backoff,
+  idle_timeout: Duration,
+) {
+  let name = client.name().get_name();
+  let disconnects = Supervisor::new(client, backoff, idle_timeout)
+    .run(|event| {
+      let mut hodler = hodler.lock().unwrap();
+
+      match event {
+        Ok(MarketEvent::Ticker(ticker)) => hodler.upsert_cryptocurrency(ticker),
+        Ok(MarketEvent::OrderBook(order_book)) => hodler.upsert_order_book(order_book),
+        Ok(MarketEvent::Trade(trade)) => hodler.upsert_trade(trade),
+        Ok(MarketEvent::Pair(pair)) => hodler.upsert_pair(pair),
+        Err(err) => hodler.record_parse_error(&name, err.get_kind()),
+      }
+    })
+    .await;
+
+  error!("{disconnects:?}");
 }